@@ -1,26 +1,36 @@
 // auth.rs
 use argon2::{
     Argon2,
-    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
+    password_hash::{
+        PasswordHash, PasswordHasher, PasswordVerifier, SaltString,
+        rand_core::{OsRng, RngCore},
+    },
 };
 use axum::{
+    Extension,
     body::Body,
     extract::State,
-    http::{Request, StatusCode},
+    http::{Request, StatusCode, header},
     middleware::Next,
     response::Response,
 };
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use axum_extra::{
     TypedHeader,
+    extract::cookie::{Cookie, CookieJar, SameSite},
     headers::{Authorization, authorization::Bearer},
 };
 use chrono::{Duration, Utc};
+use time::Duration as CookieDuration;
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use tracing;
 
+use crate::models::Role;
+use crate::state::AppState;
+
 // ユーザー名のバリデーション用 (半角英数字のみ)
 static RE_USERNAME: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"^[a-zA-Z0-9]+$").expect("Failed to compile username regex"));
@@ -28,19 +38,48 @@ static RE_USERNAME: Lazy<Regex> =
 // パスワードの最小長
 const MIN_PASSWORD_LEN: usize = 8;
 
+/// JWTを保持するCookieの名前
+pub const AUTH_COOKIE_NAME: &str = "auth_token";
+
+/// リフレッシュトークンを保持するCookieの名前
+pub const REFRESH_COOKIE_NAME: &str = "refresh_token";
+
+/// CSRFトークンを保持するCookieの名前
+/// (二重送信パターンのため、他のCookieと違いJSから読み取れる必要がある)
+pub const CSRF_COOKIE_NAME: &str = "csrf_token";
+
+/// ブラウザがCookieのCSRFトークンを送り返すヘッダー名
+pub const CSRF_HEADER_NAME: &str = "x-csrf-token";
+
 // JWTに含めるクレーム (Payload)
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
-    pub sub: i64, // Subject (user_id)
-    pub exp: i64, // Expiration time
-    pub iat: i64, // Issued at
+    pub sub: i64,           // Subject (user_id)
+    pub exp: i64,           // Expiration time
+    pub iat: i64,           // Issued at
+    pub role: Role,         // 発行時点でのユーザー権限
+    pub scopes: Vec<String>, // 発行時点でのユーザー権限から導出されたスコープ
 }
 
 // ミドルウェアがハンドラに渡すユーザー情報
 // (Extension<AuthUser> として受け取る)
+//
+// `role`/`scopes` はミドルウェアがリクエストの都度DBから読み直した値であり、
+// JWTに埋め込まれた (失効しうる) クレームそのものではない。
 #[derive(Debug, Clone)]
 pub struct AuthUser {
     pub user_id: i64,
+    pub role: Role,
+    pub scopes: Vec<String>,
+}
+
+/// ロールから導出される有効スコープの一覧
+/// (ロールが増えたときは、ここを拡張するだけでよい)
+fn scopes_for_role(role: Role) -> Vec<String> {
+    match role {
+        Role::User => vec![],
+        Role::Admin => vec!["admin:crawl".to_string()],
+    }
 }
 
 // --- JWTキーのグローバル管理 --
@@ -61,18 +100,29 @@ impl Keys {
 }
 
 /// 認証ミドルウェア
+///
+/// `Authorization: Bearer` ヘッダーと、ブラウザ向けの HttpOnly Cookie
+/// (`AUTH_COOKIE_NAME`) の両方からトークンを受け付けます。
+/// ヘッダーが優先され、無ければ Cookie を見に行きます。
 pub async fn auth_middleware(
     State(state): State<crate::state::AppState>,
-    TypedHeader(auth_header): TypedHeader<Authorization<Bearer>>,
+    jar: CookieJar,
+    auth_header: Option<TypedHeader<Authorization<Bearer>>>,
     mut request: Request<Body>,
     next: Next,
 ) -> Result<Response, (StatusCode, String)> {
-    let token = auth_header.token();
+    let token = auth_header
+        .map(|TypedHeader(header)| header.token().to_string())
+        .or_else(|| jar.get(AUTH_COOKIE_NAME).map(|c| c.value().to_string()))
+        .ok_or_else(|| {
+            tracing::warn!("Auth failed (no credentials supplied)");
+            (StatusCode::UNAUTHORIZED, "Missing credentials".to_string())
+        })?;
 
     let validation = Validation::default();
 
     // トークンをデコード (検証)
-    let claims = match decode::<Claims>(token, &state.keys.decoding, &validation) {
+    let claims = match decode::<Claims>(&token, &state.keys.decoding, &validation) {
         Ok(token_data) => token_data.claims,
         Err(e) => {
             tracing::warn!("Auth failed (invalid token): {}", e);
@@ -87,28 +137,32 @@ pub async fn auth_middleware(
         return Err((StatusCode::UNAUTHORIZED, "Token has expired".to_string()));
     }
 
-    // DBチェックを追加 (AppState から db_pool を取得)
-    let user_exists = sqlx::query("SELECT 1 FROM users WHERE user_id = ?")
-        .bind(claims.sub)
-        .fetch_optional(&state.db_pool)
-        .await
-        .map_err(|e| {
-            tracing::error!("Database error during auth: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
-        })?
-        .is_some();
-
-    if !user_exists {
+    // DBから現在のロールを取得 (存在チェックも兼ねる)
+    // トークンに埋め込まれた `role`/`scopes` ではなく、ここで都度DBを見ることで
+    // 管理者権限の剥奪がトークンの有効期限を待たずに反映されるようにする
+    let current_role: Option<Role> =
+        sqlx::query_scalar("SELECT role FROM users WHERE user_id = ?")
+            .bind(claims.sub)
+            .fetch_optional(&state.db_pool)
+            .await
+            .map_err(|e| {
+                tracing::error!("Database error during auth: {}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Internal server error".to_string(),
+                )
+            })?;
+
+    let Some(current_role) = current_role else {
         tracing::warn!("Auth failed (user not found): {}", claims.sub);
         return Err((StatusCode::UNAUTHORIZED, "User does not exist".to_string()));
-    }
+    };
 
     // リクエストにユーザー情報を添付 (Extension)
     request.extensions_mut().insert(AuthUser {
         user_id: claims.sub,
+        role: current_role,
+        scopes: scopes_for_role(current_role),
     });
 
     // 次のミドルウェアまたはハンドラを呼び出す
@@ -117,13 +171,21 @@ pub async fn auth_middleware(
 
 // --- ヘルパー関数 ---
 
-/// ユーザー登録時のバリデーションを実行します
-pub fn validate_registration(username: &str, password: &str) -> Result<(), String> {
+/// ユーザー名のバリデーションを実行します
+/// (パスワード登録・パスキー登録の両方から共通で使われます)
+pub fn validate_username(username: &str) -> Result<(), String> {
     if !RE_USERNAME.is_match(username) {
         tracing::warn!("Failed to register: Invalid username format '{}'", username);
         return Err("Username must be alphanumeric (a-z, A-Z, 0-9).".to_string());
     }
 
+    Ok(())
+}
+
+/// ユーザー登録時のバリデーションを実行します
+pub fn validate_registration(username: &str, password: &str) -> Result<(), String> {
+    validate_username(username)?;
+
     if password.len() < MIN_PASSWORD_LEN {
         tracing::warn!(
             "Failed to register: Password too short for user '{}'",
@@ -163,16 +225,208 @@ pub fn verify_password(password: &str, hash: &str) -> bool {
 }
 
 /// JWT生成
-pub fn create_jwt(user_id: i64, keys: &Keys) -> Result<String, jsonwebtoken::errors::Error> {
+///
+/// `expires_in_minutes` は `Config::jwt_expires_in` から渡され、
+/// 運用者がトークンの寿命を環境変数で調整できるようにします。
+pub fn create_jwt(
+    user_id: i64,
+    role: Role,
+    keys: &Keys,
+    expires_in_minutes: i64,
+) -> Result<String, jsonwebtoken::errors::Error> {
     let now = Utc::now();
     let iat = now.timestamp();
-    let exp = (now + Duration::days(7)).timestamp(); // 有効期限: 7日後
+    let exp = (now + Duration::minutes(expires_in_minutes)).timestamp();
 
     let claims = Claims {
         sub: user_id,
         iat,
         exp,
+        role,
+        scopes: scopes_for_role(role),
     };
 
     encode(&Header::default(), &claims, &keys.encoding)
 }
+
+/// JWTを格納する HttpOnly Cookie を構築します
+///
+/// `Secure` + `HttpOnly` + `SameSite=Lax` とし、`Max-Age` はトークンの
+/// 有効期限 (`Config::jwt_maxage`, 秒) に合わせます。
+pub fn build_auth_cookie(token: String, max_age_seconds: i64) -> Cookie<'static> {
+    Cookie::build((AUTH_COOKIE_NAME, token))
+        .path("/")
+        .http_only(true)
+        .secure(true)
+        .same_site(SameSite::Lax)
+        .max_age(CookieDuration::seconds(max_age_seconds))
+        .build()
+}
+
+/// ログアウト時にCookieを失効させるための空Cookieを構築します
+pub fn build_expired_auth_cookie() -> Cookie<'static> {
+    Cookie::build((AUTH_COOKIE_NAME, ""))
+        .path("/")
+        .http_only(true)
+        .secure(true)
+        .same_site(SameSite::Lax)
+        .max_age(CookieDuration::seconds(0))
+        .build()
+}
+
+/// リフレッシュトークンを格納する HttpOnly Cookie を構築します
+///
+/// アクセストークン用Cookieと異なり `/api/auth` 配下のみに絞り、
+/// 漏洩時の影響範囲を最小限にします。
+/// (API全体が `/api` にネストされているため、実際のエンドポイントは
+/// `/api/auth/refresh` / `/api/auth/logout` であり、Pathもそれに合わせる
+/// 必要があります。単に `/auth` にすると、ブラウザはこのCookieを
+/// `/api/auth/*` には送り返しません。)
+pub fn build_refresh_cookie(token: String, max_age_seconds: i64) -> Cookie<'static> {
+    Cookie::build((REFRESH_COOKIE_NAME, token))
+        .path("/api/auth")
+        .http_only(true)
+        .secure(true)
+        .same_site(SameSite::Lax)
+        .max_age(CookieDuration::seconds(max_age_seconds))
+        .build()
+}
+
+/// ログアウト時にリフレッシュトークンCookieを失効させるための空Cookieを構築します
+pub fn build_expired_refresh_cookie() -> Cookie<'static> {
+    Cookie::build((REFRESH_COOKIE_NAME, ""))
+        .path("/api/auth")
+        .http_only(true)
+        .secure(true)
+        .same_site(SameSite::Lax)
+        .max_age(CookieDuration::seconds(0))
+        .build()
+}
+
+/// 新しいCSRFトークン (二重送信パターン用) を生成します
+pub fn generate_csrf_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// CSRFトークンを保持するCookieを構築します
+///
+/// ブラウザのJavaScriptが値を読み取って `X-CSRF-Token` ヘッダーに
+/// 詰め直す必要があるため、他の認証Cookieと異なり `HttpOnly` にはしません。
+/// `SameSite=Strict` とすることで、クロスサイトのリクエストには
+/// そもそもこのCookie自体が付与されないようにします。
+pub fn build_csrf_cookie(token: String, max_age_seconds: i64) -> Cookie<'static> {
+    Cookie::build((CSRF_COOKIE_NAME, token))
+        .path("/")
+        .http_only(false)
+        .secure(true)
+        .same_site(SameSite::Strict)
+        .max_age(CookieDuration::seconds(max_age_seconds))
+        .build()
+}
+
+/// ログアウト時にCSRFトークンCookieを失効させるための空Cookieを構築します
+pub fn build_expired_csrf_cookie() -> Cookie<'static> {
+    Cookie::build((CSRF_COOKIE_NAME, ""))
+        .path("/")
+        .http_only(false)
+        .secure(true)
+        .same_site(SameSite::Strict)
+        .max_age(CookieDuration::seconds(0))
+        .build()
+}
+
+/// 二重送信 (double-submit cookie) パターンによるCSRF対策ミドルウェア
+///
+/// GET/HEAD/OPTIONS のような安全なメソッドや、`Authorization` ヘッダーで
+/// Bearerトークンを送るクライアント (ブラウザのCookieに依存しないため
+/// CSRF攻撃の対象にならない) は対象外とします。それ以外のリクエストでは
+/// `CSRF_COOKIE_NAME` Cookie と `CSRF_HEADER_NAME` ヘッダーの値が一致する
+/// ことを要求します。
+pub async fn csrf_protection(
+    jar: CookieJar,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, (StatusCode, String)> {
+    let is_safe_method = matches!(
+        *request.method(),
+        axum::http::Method::GET | axum::http::Method::HEAD | axum::http::Method::OPTIONS
+    );
+    let uses_bearer_auth = request.headers().contains_key(header::AUTHORIZATION);
+
+    if is_safe_method || uses_bearer_auth {
+        return Ok(next.run(request).await);
+    }
+
+    let cookie_token = jar.get(CSRF_COOKIE_NAME).map(|c| c.value().to_string());
+    let header_token = request
+        .headers()
+        .get(CSRF_HEADER_NAME)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+
+    match (cookie_token, header_token) {
+        (Some(cookie_token), Some(header_token)) if cookie_token == header_token => {
+            Ok(next.run(request).await)
+        }
+        _ => {
+            tracing::warn!(
+                "CSRF check failed for {} {}",
+                request.method(),
+                request.uri()
+            );
+            Err((
+                StatusCode::FORBIDDEN,
+                "Missing or invalid CSRF token".to_string(),
+            ))
+        }
+    }
+}
+
+// --- スコープベース認可 ---
+
+/// `RequireScope` ミドルウェアに渡す設定
+/// (`AppState` に加えて必要なスコープを保持するため、専用の state 型にしている)
+#[derive(Clone)]
+pub struct RequireScope {
+    pub app_state: AppState,
+    pub required_scope: &'static str,
+}
+
+impl RequireScope {
+    pub fn new(app_state: AppState, required_scope: &'static str) -> Self {
+        Self {
+            app_state,
+            required_scope,
+        }
+    }
+}
+
+/// スコープに基づく認可ミドルウェア
+///
+/// `auth_middleware` が Extension に添付した `AuthUser` のスコープ一覧に
+/// `required_scope` が含まれない場合、403 Forbidden で短絡します。
+/// ルーターには `middleware::from_fn_with_state(RequireScope::new(...), require_scope)`
+/// の形でレイヤーとして適用します (例: `RequireScope::new(state, "admin:crawl")`)。
+pub async fn require_scope(
+    State(gate): State<RequireScope>,
+    Extension(auth_user): Extension<AuthUser>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, (StatusCode, String)> {
+    if !auth_user.scopes.iter().any(|s| s == gate.required_scope) {
+        tracing::warn!(
+            "Authorization failed: user {} (scopes {:?}) lacks required scope '{}'",
+            auth_user.user_id,
+            auth_user.scopes,
+            gate.required_scope
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Insufficient permissions".to_string(),
+        ));
+    }
+
+    Ok(next.run(request).await)
+}
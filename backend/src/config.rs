@@ -0,0 +1,78 @@
+// src/config.rs
+
+/// アプリケーション設定
+///
+/// `main.rs` に散らばっていた `std::env::var(...)` 呼び出しを集約し、
+/// 起動時に一度だけ検証します。
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub database_url: String,
+    pub jwt_secret: String,
+    /// アクセストークン (JWT) の有効期限 (分)。漏洩時の被害を抑えるため短命にし、
+    /// セッションの継続は `refresh_tokens` によるリフレッシュトークンに任せる。
+    pub jwt_expires_in: i64,
+    /// アクセストークンCookie の Max-Age 等に使う有効期限 (秒)
+    pub jwt_maxage: i64,
+    /// リフレッシュトークンの有効期限 (日)
+    pub refresh_token_expires_in_days: i64,
+    pub port: u16,
+    pub static_dir: String,
+    /// WebAuthn の Relying Party ID (通常はフロントエンドのホスト名)
+    pub webauthn_rp_id: String,
+    /// WebAuthn の Relying Party Origin (スキーム込みのフロントエンドのオリジン)
+    pub webauthn_origin: String,
+}
+
+impl Config {
+    /// 環境変数からアプリケーション設定を読み込みます。
+    ///
+    /// `JWT_SECRET` が未設定、または空の場合は起動時点で panic させ、
+    /// 不完全な設定のままサーバーが起動することを防ぎます。
+    pub fn init() -> Self {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or_else(|_| "sqlite:./papers.sqlite".to_string());
+
+        let jwt_secret = std::env::var("JWT_SECRET")
+            .expect("JWT_SECRET must be set (no default is provided for security reasons)");
+        if jwt_secret.trim().is_empty() {
+            panic!("JWT_SECRET must not be empty");
+        }
+
+        let jwt_expires_in: i64 = std::env::var("JWT_EXPIRES_IN_MINUTES")
+            .unwrap_or_else(|_| "15".to_string())
+            .parse()
+            .expect("JWT_EXPIRES_IN_MINUTES must be a valid integer (minutes)");
+
+        let jwt_maxage = jwt_expires_in * 60;
+
+        let refresh_token_expires_in_days: i64 = std::env::var("REFRESH_TOKEN_EXPIRES_IN_DAYS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .expect("REFRESH_TOKEN_EXPIRES_IN_DAYS must be a valid integer (days)");
+
+        let port: u16 = std::env::var("PORT")
+            .unwrap_or_else(|_| "3000".to_string())
+            .parse()
+            .expect("PORT must be a valid port number");
+
+        let static_dir =
+            std::env::var("STATIC_DIR").unwrap_or_else(|_| "../frontend/dist".to_string());
+
+        let webauthn_rp_id =
+            std::env::var("WEBAUTHN_RP_ID").unwrap_or_else(|_| "localhost".to_string());
+        let webauthn_origin = std::env::var("WEBAUTHN_ORIGIN")
+            .unwrap_or_else(|_| "http://localhost:3000".to_string());
+
+        Self {
+            database_url,
+            jwt_secret,
+            jwt_expires_in,
+            jwt_maxage,
+            refresh_token_expires_in_days,
+            port,
+            static_dir,
+            webauthn_rp_id,
+            webauthn_origin,
+        }
+    }
+}
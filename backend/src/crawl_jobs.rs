@@ -0,0 +1,148 @@
+// src/crawl_jobs.rs
+use sqlx::{Pool, Sqlite};
+use tokio::sync::mpsc;
+use tracing;
+
+/// `crawl_jobs.id` をバックグラウンドワーカーに渡すためのキューのハンドル
+///
+/// 送信側 (`enqueue`) は `trigger_crawl` ハンドラから、受信側は起動時に
+/// spawn される単一のワーカータスクから使われます。ジョブを単一タスクが
+/// 直列に処理することで、複数のクロールが同時に同じサイトへ殺到したり、
+/// DBへの書き込みが競合したりするのを防ぎます。
+#[derive(Clone)]
+pub struct CrawlJobQueue {
+    sender: mpsc::UnboundedSender<i64>,
+}
+
+impl CrawlJobQueue {
+    /// ワーカータスクを起動し、ジョブを投入するためのハンドルを返す
+    /// (main.rs で起動時に一度だけ呼び出します)
+    ///
+    /// 起動時点で `Queued`/`Running` のまま残っているジョブ (前回プロセスが
+    /// 再起動・クラッシュした際にチャネルごと消えてしまったもの) を
+    /// DBから拾い直し、ワーカーに再投入してから受信ループに入ります。
+    /// これにより、プロセスを再起動してもジョブが永久にスタックしません。
+    pub fn spawn(db_pool: Pool<Sqlite>) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let worker_sender = sender.clone();
+        tokio::spawn(async move {
+            requeue_outstanding_jobs(&db_pool, &worker_sender).await;
+            worker_loop(db_pool, receiver).await;
+        });
+        Self { sender }
+    }
+
+    /// ジョブをワーカーに投入する。
+    ///
+    /// チャネルが閉じている (ワーカーがパニックした等) 場合でもジョブは
+    /// `Queued` のままDBに残るだけなので、サーバー自体は落とさずログに残す。
+    pub fn enqueue(&self, job_id: i64) {
+        if self.sender.send(job_id).is_err() {
+            tracing::error!(
+                "Crawl job worker channel is closed; job {} stays queued",
+                job_id
+            );
+        }
+    }
+}
+
+/// 起動時点で `Queued`/`Running` のまま残っているジョブをワーカーに再投入する
+///
+/// `Running` は前回プロセスがクロール処理の途中で落ちたことを意味するため、
+/// 安全に最初からやり直せるよう一旦 `Queued` に戻してから投入します。
+async fn requeue_outstanding_jobs(db_pool: &Pool<Sqlite>, sender: &mpsc::UnboundedSender<i64>) {
+    if let Err(e) = sqlx::query("UPDATE crawl_jobs SET status = 'Queued' WHERE status = 'Running'")
+        .execute(db_pool)
+        .await
+    {
+        tracing::error!("Failed to reset stale 'Running' crawl jobs to 'Queued': {}", e);
+        return;
+    }
+
+    let job_ids: Vec<(i64,)> = match sqlx::query_as(
+        "SELECT id FROM crawl_jobs WHERE status = 'Queued' ORDER BY id ASC",
+    )
+    .fetch_all(db_pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::error!("Failed to load outstanding crawl jobs on startup: {}", e);
+            return;
+        }
+    };
+
+    for (job_id,) in job_ids {
+        tracing::info!("Re-enqueuing outstanding crawl job {} after restart", job_id);
+        if sender.send(job_id).is_err() {
+            tracing::error!(
+                "Crawl job worker channel is closed while re-enqueuing job {}; job stays queued",
+                job_id
+            );
+            break;
+        }
+    }
+}
+
+/// キューに投入されたジョブIDを1件ずつ直列に処理し続けるワーカーループ
+async fn worker_loop(db_pool: Pool<Sqlite>, mut receiver: mpsc::UnboundedReceiver<i64>) {
+    tracing::info!("Crawl job worker started");
+
+    while let Some(job_id) = receiver.recv().await {
+        if let Err(e) = process_job(&db_pool, job_id).await {
+            tracing::error!("Crawl job {} could not be processed: {}", job_id, e);
+        }
+    }
+
+    tracing::warn!("Crawl job worker loop ended (channel closed)");
+}
+
+/// 1件のジョブを `Running` に遷移させ、クロール結果に応じて
+/// `Succeeded`/`Failed` へ遷移させる
+async fn process_job(db_pool: &Pool<Sqlite>, job_id: i64) -> Result<(), sqlx::Error> {
+    let row: Option<(String,)> = sqlx::query_as("SELECT urls FROM crawl_jobs WHERE id = ?")
+        .bind(job_id)
+        .fetch_optional(db_pool)
+        .await?;
+
+    let Some((urls_json,)) = row else {
+        tracing::error!("Crawl job {} not found when starting", job_id);
+        return Ok(());
+    };
+
+    let urls: Vec<String> = serde_json::from_str(&urls_json).unwrap_or_default();
+
+    sqlx::query("UPDATE crawl_jobs SET status = 'Running' WHERE id = ?")
+        .bind(job_id)
+        .execute(db_pool)
+        .await?;
+
+    match crate::crawler::run_crawl_job(db_pool, job_id, urls).await {
+        Ok(summary) => {
+            sqlx::query(
+                "UPDATE crawl_jobs
+                 SET status = 'Succeeded', summary = ?, finished_at = datetime('now')
+                 WHERE id = ?",
+            )
+            .bind(&summary)
+            .bind(job_id)
+            .execute(db_pool)
+            .await?;
+            tracing::info!("Crawl job {} succeeded: {}", job_id, summary);
+        }
+        Err(err) => {
+            sqlx::query(
+                "UPDATE crawl_jobs
+                 SET status = 'Failed', summary = ?, finished_at = datetime('now')
+                 WHERE id = ?",
+            )
+            .bind(&err)
+            .bind(job_id)
+            .execute(db_pool)
+            .await?;
+            tracing::error!("Crawl job {} failed: {}", job_id, err);
+        }
+    }
+
+    Ok(())
+}
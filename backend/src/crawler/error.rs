@@ -4,13 +4,14 @@ use std::fmt;
 
 /// クローラーモジュール専用のエラー型
 #[derive(Debug)]
-pub(super) enum CrawlError {
+pub(crate) enum CrawlError {
     Fetch(reqwest::Error),
     Http(String),
     Parse(String),
     Database(sqlx::Error),
     Url(url::ParseError),
-    NoParserFound(String),
+    UnsupportedSource(String),
+    RobotsDisallowed(String),
 }
 
 impl fmt::Display for CrawlError {
@@ -21,7 +22,12 @@ impl fmt::Display for CrawlError {
             CrawlError::Parse(s) => write!(f, "Parsing error: {}", s),
             CrawlError::Database(e) => write!(f, "Database error: {}", e),
             CrawlError::Url(e) => write!(f, "Invalid URL: {}", e),
-            CrawlError::NoParserFound(url) => write!(f, "No parser found for URL: {}", url),
+            CrawlError::UnsupportedSource(url) => {
+                write!(f, "Unsupported source (no parser registered for host): {}", url)
+            }
+            CrawlError::RobotsDisallowed(url) => {
+                write!(f, "Crawling disallowed by robots.txt: {}", url)
+            }
         }
     }
 }
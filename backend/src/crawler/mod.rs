@@ -1,15 +1,31 @@
 // api/src/crawler/mod.rs
 
 mod error;
+mod parser_acm;
+mod parser_arxiv;
+mod parser_openreview;
 mod parser_usenix;
+mod politeness;
 
-use error::CrawlError;
+pub(crate) use error::CrawlError;
+use parser_acm::AcmParser;
+use parser_arxiv::ArxivParser;
+use parser_openreview::OpenReviewParser;
 use parser_usenix::UsenixParser;
+use politeness::{CRAWLER_USER_AGENT, HostRateLimiter, RobotsCache};
+use std::collections::HashMap;
 
+use futures::stream::{self, StreamExt};
+use once_cell::sync::Lazy;
 use sqlx::{Sqlite, SqlitePool, Transaction};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
 use tracing;
 use url::Url;
 
+/// 同時にフェッチするURLの最大数
+const MAX_CONCURRENT_FETCHES: usize = 4;
+
 /// 抽出した論文情報を保持する構造体 (DB挿入用)
 // (parser_usenix.rs からも参照されるため、pub(super) または pub にする)
 #[derive(Debug, Clone)]
@@ -25,8 +41,15 @@ pub(super) struct Paper {
 /// すべてのWebサイト固有パーサーのための共通トレイト
 trait PaperParser
 where
-    Self: Send,
+    Self: Send + Sync,
 {
+    /// クロールサマリーに表示する、このパーサーの名前 (例: "USENIX")
+    fn name(&self) -> &'static str;
+
+    /// このパーサーが対応するホスト名の部分文字列パターン
+    /// (例: `usenix.org` は `www.usenix.org` にもマッチする)
+    fn host_patterns(&self) -> &'static [&'static str];
+
     /// HTMLコンテンツをパースし、論文情報のリストを抽出する
     fn parse_and_extract(
         &self,
@@ -35,23 +58,31 @@ where
     ) -> Result<Vec<Paper>, CrawlError>;
 }
 
+/// 利用可能な全パーサーのレジストリ
+/// (新しいサイトに対応するときは、`PaperParser` を実装し、ここに1行追加するだけでよい)
+static PARSERS: Lazy<Vec<Box<dyn PaperParser>>> = Lazy::new(|| {
+    vec![
+        Box::new(UsenixParser),
+        Box::new(ArxivParser),
+        Box::new(OpenReviewParser),
+        Box::new(AcmParser),
+    ]
+});
+
 /// URLのホスト名に基づいて適切なパーサーを選択する
-fn get_parser(url_str: &str) -> Result<Box<dyn PaperParser>, CrawlError> {
+fn get_parser(url_str: &str) -> Result<&'static dyn PaperParser, CrawlError> {
     // url クレートを使い、URLを安全にパース
     let url = Url::parse(url_str)?;
     let host = url.host_str().unwrap_or_default();
 
-    if host.contains("usenix.org") {
-        tracing::debug!("Using UsenixParser for: {}", url_str);
-        Ok(Box::new(UsenixParser))
-    } else {
-        // TODO: 将来的に他のパーサーを追加 (例: acm.org, ieee.org)
-        // } else if host.contains("acm.org") {
-        //     Ok(Box::new(AcmParser))
-        // }
-        tracing::warn!("No parser found for host: {}", host);
-        Err(CrawlError::NoParserFound(url_str.to_string()))
-    }
+    PARSERS
+        .iter()
+        .find(|parser| parser.host_patterns().iter().any(|pattern| host.contains(pattern)))
+        .map(|parser| parser.as_ref())
+        .ok_or_else(|| {
+            tracing::warn!("No parser found for host: {}", host);
+            CrawlError::UnsupportedSource(url_str.to_string())
+        })
 }
 
 // --- データベースロジック ---
@@ -85,9 +116,8 @@ async fn insert_papers(
 
 // --- HTMLフェッチロジック ---
 /// 指定されたURLからHTMLコンテンツを非同期で取得する
-async fn fetch_html(url: &str) -> Result<String, CrawlError> {
+async fn fetch_html(client: &reqwest::Client, url: &str) -> Result<String, CrawlError> {
     tracing::info!("Fetching HTML from: {}", url);
-    let client = reqwest::Client::new();
     let response = client.get(url).send().await?;
 
     if !response.status().is_success() {
@@ -103,88 +133,193 @@ async fn fetch_html(url: &str) -> Result<String, CrawlError> {
     Ok(html_content)
 }
 
+/// 1件のURLについて、robots.txt確認・レート制限・フェッチ・パースまでを行う
+async fn fetch_and_parse_one(
+    client: reqwest::Client,
+    host_limiter: Arc<HostRateLimiter>,
+    robots_cache: Arc<RobotsCache>,
+    url_str: String,
+) -> (String, Option<&'static str>, Result<Vec<Paper>, CrawlError>) {
+    // URLに基づいてパーサーを動的に選択 (ホスト名が分からないとこのあとの処理が
+    // そもそもできないため最初に行う)。選択されたパーサー名は、見つからなかった
+    // 場合も含めてクロールサマリーに出すため、結果とは別に保持しておく。
+    let parser = match get_parser(&url_str) {
+        Ok(parser) => parser,
+        Err(e) => return (url_str, None, Err(e)),
+    };
+
+    let outcome = async {
+        let url = Url::parse(&url_str)?;
+        let host = url.host_str().unwrap_or_default().to_string();
+
+        // robots.txt を確認し、クロールが許可されているか確認
+        if !robots_cache.is_allowed(&client, &url).await {
+            return Err(CrawlError::RobotsDisallowed(url_str.clone()));
+        }
+
+        // 同一ホストへの最小アクセス間隔を守る
+        host_limiter.wait_for_turn(&host).await;
+
+        // HTMLのフェッチとパース
+        let html_content = fetch_html(&client, &url_str).await?;
+        parser.parse_and_extract(&html_content, &url_str)
+    }
+    .await;
+
+    (url_str, Some(parser.name()), outcome)
+}
+
 // --- 実行の起点となる関数 ---
 
+/// 処理済みのURL1件分の進捗を `crawl_jobs` テーブルに書き戻す
+///
+/// ジョブの行が見つからない等の失敗はクロール自体を止める理由にはならないため、
+/// ログに残すのみで継続します。
+async fn update_job_progress(db_pool: &SqlitePool, job_id: i64, found: usize, inserted: usize) {
+    let result = sqlx::query(
+        "UPDATE crawl_jobs
+         SET processed_urls = processed_urls + 1,
+             papers_found = papers_found + ?,
+             papers_inserted = papers_inserted + ?
+         WHERE id = ?",
+    )
+    .bind(found as i64)
+    .bind(inserted as i64)
+    .bind(job_id)
+    .execute(db_pool)
+    .await;
+
+    if let Err(e) = result {
+        tracing::error!("Failed to update progress for crawl job {}: {}", job_id, e);
+    }
+}
+
 /// クローリングのコアロジック (内部関数)
-/// エラーが発生しても（DBエラー以外）、次のURLの処理を続行します。
+///
+/// 複数URLを `MAX_CONCURRENT_FETCHES` 件まで並行してフェッチします。
+/// DB挿入はURLごとに独立したトランザクションで行うため、1件の失敗が
+/// 他のURLの結果まで巻き戻すことはありません。URLを1件処理するたびに
+/// `job_id` に対応する `crawl_jobs` 行の進捗カウントを更新します。
 async fn run_crawl_logic(
     db_pool: &SqlitePool,
+    job_id: i64,
     urls: Vec<String>,
-) -> Result<(usize, usize), CrawlError> {
-    let mut total_papers_inserted = 0;
-    let mut total_papers_found = 0;
+) -> Result<(usize, usize, HashMap<&'static str, (usize, usize)>), CrawlError> {
+    let client = reqwest::Client::builder()
+        .user_agent(CRAWLER_USER_AGENT)
+        .build()?;
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_FETCHES));
+    let host_limiter = Arc::new(HostRateLimiter::new());
+    let robots_cache = Arc::new(RobotsCache::new());
 
-    let mut tx = db_pool.begin().await?;
+    let fetch_results = stream::iter(urls.into_iter().map(|url_str| {
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        let host_limiter = host_limiter.clone();
+        let robots_cache = robots_cache.clone();
+
+        async move {
+            // セマフォで同時実行数を制限する
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .expect("crawl semaphore should never be closed");
+            fetch_and_parse_one(client, host_limiter, robots_cache, url_str).await
+        }
+    }))
+    .buffer_unordered(MAX_CONCURRENT_FETCHES)
+    .collect::<Vec<_>>()
+    .await;
 
-    for url_str in &urls {
-        // 1. URLに基づいてパーサーを動的に選択
-        let parser = match get_parser(url_str) {
-            Ok(p) => p,
+    let mut total_papers_found = 0;
+    let mut total_papers_inserted = 0;
+    // サマリーに表示する、パーサーごとの (見つかった件数, 新規挿入件数)
+    let mut per_parser_counts: HashMap<&'static str, (usize, usize)> = HashMap::new();
+
+    for (url_str, parser_name, outcome) in fetch_results {
+        let papers = match outcome {
+            Ok(papers) => papers,
             Err(e) => {
-                // パーサーが見つからない場合はエラーをログに記録し、次のURLへ
-                tracing::error!("Skipping URL: {}", e);
-                continue; // ループの次のイテレーションへ
+                // 1つのURLのフェッチ/パース/robots.txt拒否は、他のURLの処理を止めない
+                tracing::error!("Skipping URL {}: {}", url_str, e);
+                update_job_progress(db_pool, job_id, 0, 0).await;
+                continue;
             }
         };
 
-        // 1つのURLのフェッチやパースに失敗しても、ループを継続する
-        // 2. HTMLのフェッチ
-        match fetch_html(url_str).await {
-            Ok(html_content) => {
-                // 3. 選択されたパーサーでパース
-                match parser.parse_and_extract(&html_content, url_str) {
-                    Ok(papers) => {
-                        let num_found = papers.len();
-                        total_papers_found += num_found;
-
-                        if num_found > 0 {
-                            // 4. DB挿入
-                            match insert_papers(&mut tx, &papers).await {
-                                Ok(inserted) => {
-                                    total_papers_inserted += inserted;
-                                    tracing::info!(
-                                        "Inserted {} new papers from {}",
-                                        inserted,
-                                        url_str
-                                    );
-                                }
-                                Err(db_err) => {
-                                    tracing::error!(
-                                        "Database insertion error for {}: {}. Rolling back.",
-                                        url_str,
-                                        db_err
-                                    );
-                                    let _ = tx.rollback().await; // ロールバックを試みる
-                                    return Err(db_err.into());
-                                }
-                            }
-                        }
-                    }
-                    Err(parse_err) => {
-                        tracing::error!("Error parsing/extracting from {}: {}", url_str, parse_err);
-                    }
+        total_papers_found += papers.len();
+        if papers.is_empty() {
+            update_job_progress(db_pool, job_id, 0, 0).await;
+            continue;
+        }
+
+        // URLごとに独立したトランザクションでDBへ挿入する
+        let mut tx = db_pool.begin().await?;
+        match insert_papers(&mut tx, &papers).await {
+            Ok(inserted) => {
+                tx.commit().await?;
+                total_papers_inserted += inserted;
+                tracing::info!("Inserted {} new papers from {}", inserted, url_str);
+                update_job_progress(db_pool, job_id, papers.len(), inserted).await;
+
+                if let Some(parser_name) = parser_name {
+                    let entry = per_parser_counts.entry(parser_name).or_insert((0, 0));
+                    entry.0 += papers.len();
+                    entry.1 += inserted;
                 }
             }
-            Err(fetch_err) => {
-                tracing::error!("Error fetching URL {}: {}", url_str, fetch_err);
+            Err(db_err) => {
+                tracing::error!(
+                    "Database insertion error for {}: {}. Rolling back this URL only.",
+                    url_str,
+                    db_err
+                );
+                let _ = tx.rollback().await;
+                update_job_progress(db_pool, job_id, papers.len(), 0).await;
+
+                if let Some(parser_name) = parser_name {
+                    per_parser_counts.entry(parser_name).or_insert((0, 0)).0 += papers.len();
+                }
             }
         }
     }
 
-    // すべて成功したらコミット
-    tx.commit().await?;
-
-    Ok((total_papers_found, total_papers_inserted))
+    Ok((total_papers_found, total_papers_inserted, per_parser_counts))
 }
 
 /// クローリングを実行し、DBプールにデータを挿入します (公開API)
-pub async fn run_crawl(db_pool: &SqlitePool, urls: Vec<String>) -> Result<String, String> {
-    match run_crawl_logic(db_pool, urls).await {
-        Ok((total_papers_found, total_papers_inserted)) => {
-            let summary = format!(
-                "Crawl complete. Total papers found: {}. Total new papers inserted: {}",
-                total_papers_found, total_papers_inserted
-            );
+///
+/// `job_id` に対応する `crawl_jobs` 行の進捗を更新しながら実行します。
+/// ジョブ自体のステータス遷移 (`Queued` -> `Running` -> `Succeeded`/`Failed`) は
+/// 呼び出し元の `crawl_jobs` ワーカーが担います。
+pub async fn run_crawl_job(
+    db_pool: &SqlitePool,
+    job_id: i64,
+    urls: Vec<String>,
+) -> Result<String, String> {
+    match run_crawl_logic(db_pool, job_id, urls).await {
+        Ok((total_papers_found, total_papers_inserted, per_parser_counts)) => {
+            let mut per_parser_breakdown = per_parser_counts
+                .into_iter()
+                .map(|(name, (found, inserted))| {
+                    format!("{}: {} found, {} new", name, found, inserted)
+                })
+                .collect::<Vec<_>>();
+            per_parser_breakdown.sort();
+
+            let summary = if per_parser_breakdown.is_empty() {
+                format!(
+                    "Crawl complete. Total papers found: {}. Total new papers inserted: {}",
+                    total_papers_found, total_papers_inserted
+                )
+            } else {
+                format!(
+                    "Crawl complete. Total papers found: {}. Total new papers inserted: {}. By source: {}",
+                    total_papers_found,
+                    total_papers_inserted,
+                    per_parser_breakdown.join("; ")
+                )
+            };
             tracing::info!("{}", summary);
             Ok(summary)
         }
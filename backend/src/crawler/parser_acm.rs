@@ -0,0 +1,128 @@
+// src/crawler/parser_acm.rs
+use super::{CrawlError, Paper, PaperParser};
+use chrono::{Datelike, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::{Html, Selector};
+use url::Url;
+
+// --- ACM Digital Library パーサー ---
+//
+// ACM DLの会議プロシーディングス目次ページ (例: https://dl.acm.org/doi/proceedings/10.1145/XXXXXXX)
+// は、USENIXと同様に1ページに複数論文が並ぶ一覧形式。
+
+struct AcmSelectors {
+    issue_item: Selector,
+    title_link: Selector,
+    authors: Selector,
+    abstract_text: Selector,
+    page_title: Selector,
+}
+
+static SELECTORS: Lazy<AcmSelectors> = Lazy::new(|| AcmSelectors {
+    issue_item: Selector::parse("div.issue-item").expect("Failed to parse ACM issue-item selector"),
+    title_link: Selector::parse("h5.issue-item__title a")
+        .expect("Failed to parse ACM title link selector"),
+    authors: Selector::parse(".issue-item__authors, .loa a")
+        .expect("Failed to parse ACM authors selector"),
+    abstract_text: Selector::parse(".issue-item__abstract")
+        .expect("Failed to parse ACM abstract selector"),
+    page_title: Selector::parse("head > title").expect("Failed to parse ACM page title selector"),
+});
+
+// ページタイトルから開催年の西暦4桁を拾う (例: "... Proceedings of ... 2023 ...")
+static RE_YEAR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(19|20)\d{2}\b").expect("Failed to compile ACM year regex"));
+
+pub(super) struct AcmParser;
+
+impl PaperParser for AcmParser {
+    fn name(&self) -> &'static str {
+        "ACM Digital Library"
+    }
+
+    fn host_patterns(&self) -> &'static [&'static str] {
+        &["dl.acm.org"]
+    }
+
+    fn parse_and_extract(
+        &self,
+        html_content: &str,
+        url_str: &str,
+    ) -> Result<Vec<Paper>, CrawlError> {
+        let document = Html::parse_document(html_content);
+        let base_url = Url::parse(url_str)?;
+
+        let conference_name = document
+            .select(&SELECTORS.page_title)
+            .next()
+            .map(|el| {
+                el.text()
+                    .collect::<String>()
+                    .trim()
+                    .split('|')
+                    .next()
+                    .unwrap_or("Unknown ACM Proceedings")
+                    .trim()
+                    .to_string()
+            })
+            .unwrap_or_else(|| "Unknown ACM Proceedings".to_string());
+
+        let year = RE_YEAR
+            .find(&conference_name)
+            .and_then(|m| m.as_str().parse::<i32>().ok())
+            .unwrap_or_else(|| Utc::now().year());
+
+        let mut papers = Vec::new();
+
+        for item in document.select(&SELECTORS.issue_item) {
+            let mut title = "Paper title not found".to_string();
+            let mut paper_url = "Paper URL not found".to_string();
+
+            if let Some(title_link_el) = item.select(&SELECTORS.title_link).next() {
+                title = title_link_el.text().collect::<String>().trim().to_string();
+                if let Some(href) = title_link_el.value().attr("href") {
+                    match base_url.join(href) {
+                        Ok(full_url) => paper_url = full_url.to_string(),
+                        Err(e) => {
+                            paper_url =
+                                format!("Failed to join URL: {} with base {}: {}", href, base_url, e);
+                        }
+                    }
+                }
+            }
+
+            let authors = item
+                .select(&SELECTORS.authors)
+                .map(|el| el.text().collect::<String>().trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let authors = if authors.is_empty() {
+                "Authors not found".to_string()
+            } else {
+                authors
+            };
+
+            // ACMの目次ページには多くの場合アブストラクトの全文は載らないため、
+            // 取得できなければ "Abstract not found" とする
+            let abstract_text = item
+                .select(&SELECTORS.abstract_text)
+                .next()
+                .map(|el| el.text().collect::<String>().trim().to_string())
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(|| "Abstract not found".to_string());
+
+            papers.push(Paper {
+                conference_name: conference_name.clone(),
+                year,
+                title,
+                url: paper_url,
+                authors,
+                abstract_text,
+            });
+        }
+
+        Ok(papers)
+    }
+}
@@ -0,0 +1,104 @@
+// src/crawler/parser_arxiv.rs
+use super::{CrawlError, Paper, PaperParser};
+use chrono::{Datelike, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::{Html, Selector};
+
+// --- arXiv パーサー ---
+//
+// arXiv のアブストラクトページ (例: https://arxiv.org/abs/2301.12345) は
+// USENIXの一覧ページと違い1ページ=1論文なので、1件の `Paper` のみを返す。
+
+struct ArxivSelectors {
+    title: Selector,
+    authors: Selector,
+    abstract_blockquote: Selector,
+}
+
+static SELECTORS: Lazy<ArxivSelectors> = Lazy::new(|| ArxivSelectors {
+    title: Selector::parse("h1.title").expect("Failed to parse arXiv title selector"),
+    authors: Selector::parse("div.authors").expect("Failed to parse arXiv authors selector"),
+    abstract_blockquote: Selector::parse("blockquote.abstract")
+        .expect("Failed to parse arXiv abstract selector"),
+});
+
+// URLのarXiv ID (例: 2301.12345) の先頭2桁から投稿年を復元する (YYMM.NNNNN 形式)
+static RE_ARXIV_ID: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\d{2})\d{2}\.\d{4,5}").expect("Failed to compile arXiv ID regex"));
+
+pub(super) struct ArxivParser;
+
+impl PaperParser for ArxivParser {
+    fn name(&self) -> &'static str {
+        "arXiv"
+    }
+
+    fn host_patterns(&self) -> &'static [&'static str] {
+        &["arxiv.org"]
+    }
+
+    fn parse_and_extract(
+        &self,
+        html_content: &str,
+        url_str: &str,
+    ) -> Result<Vec<Paper>, CrawlError> {
+        let document = Html::parse_document(html_content);
+
+        let title = document
+            .select(&SELECTORS.title)
+            .next()
+            .map(|el| {
+                // "Title:" というラベルの接頭辞が付いているので取り除く
+                el.text()
+                    .collect::<String>()
+                    .trim()
+                    .trim_start_matches("Title:")
+                    .trim()
+                    .to_string()
+            })
+            .ok_or_else(|| CrawlError::Parse("arXiv paper title not found".to_string()))?;
+
+        let authors = document
+            .select(&SELECTORS.authors)
+            .next()
+            .map(|el| {
+                el.text()
+                    .collect::<String>()
+                    .trim()
+                    .trim_start_matches("Authors:")
+                    .trim()
+                    .to_string()
+            })
+            .unwrap_or_else(|| "Authors not found".to_string());
+
+        let abstract_text = document
+            .select(&SELECTORS.abstract_blockquote)
+            .next()
+            .map(|el| {
+                el.text()
+                    .collect::<String>()
+                    .trim()
+                    .trim_start_matches("Abstract:")
+                    .trim()
+                    .to_string()
+            })
+            .unwrap_or_else(|| "Abstract not found".to_string());
+
+        let year = RE_ARXIV_ID
+            .captures(url_str)
+            .and_then(|caps| caps.get(1))
+            .and_then(|m| m.as_str().parse::<i32>().ok())
+            .map(|yy| 2000 + yy)
+            .unwrap_or_else(|| Utc::now().year());
+
+        Ok(vec![Paper {
+            conference_name: "arXiv".to_string(),
+            year,
+            title,
+            url: url_str.to_string(),
+            authors,
+            abstract_text,
+        }])
+    }
+}
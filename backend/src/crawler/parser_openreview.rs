@@ -0,0 +1,81 @@
+// src/crawler/parser_openreview.rs
+use super::{CrawlError, Paper, PaperParser};
+use chrono::{Datelike, Utc};
+use once_cell::sync::Lazy;
+use scraper::{Html, Selector};
+
+// --- OpenReview パーサー ---
+//
+// OpenReviewのフォーラムページはクライアントサイドレンダリングのため、
+// 本文のDOMではなく検索エンジン向けに埋め込まれた `<meta name="citation_*">`
+// タグから情報を抽出する (1ページ=1論文)。
+
+struct OpenReviewSelectors {
+    citation_title: Selector,
+    citation_author: Selector,
+    citation_abstract: Selector,
+}
+
+static SELECTORS: Lazy<OpenReviewSelectors> = Lazy::new(|| OpenReviewSelectors {
+    citation_title: Selector::parse(r#"meta[name="citation_title"]"#)
+        .expect("Failed to parse OpenReview citation_title selector"),
+    citation_author: Selector::parse(r#"meta[name="citation_author"]"#)
+        .expect("Failed to parse OpenReview citation_author selector"),
+    citation_abstract: Selector::parse(r#"meta[name="citation_abstract"]"#)
+        .expect("Failed to parse OpenReview citation_abstract selector"),
+});
+
+pub(super) struct OpenReviewParser;
+
+impl PaperParser for OpenReviewParser {
+    fn name(&self) -> &'static str {
+        "OpenReview"
+    }
+
+    fn host_patterns(&self) -> &'static [&'static str] {
+        &["openreview.net"]
+    }
+
+    fn parse_and_extract(
+        &self,
+        html_content: &str,
+        url_str: &str,
+    ) -> Result<Vec<Paper>, CrawlError> {
+        let document = Html::parse_document(html_content);
+
+        let title = document
+            .select(&SELECTORS.citation_title)
+            .next()
+            .and_then(|el| el.value().attr("content"))
+            .map(|s| s.trim().to_string())
+            .ok_or_else(|| CrawlError::Parse("OpenReview citation_title not found".to_string()))?;
+
+        // citation_author は著者1人につき1つのmetaタグで複数出現する
+        let authors = document
+            .select(&SELECTORS.citation_author)
+            .filter_map(|el| el.value().attr("content"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let authors = if authors.is_empty() {
+            "Authors not found".to_string()
+        } else {
+            authors
+        };
+
+        let abstract_text = document
+            .select(&SELECTORS.citation_abstract)
+            .next()
+            .and_then(|el| el.value().attr("content"))
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|| "Abstract not found".to_string());
+
+        Ok(vec![Paper {
+            conference_name: "OpenReview".to_string(),
+            year: Utc::now().year(),
+            title,
+            url: url_str.to_string(),
+            authors,
+            abstract_text,
+        }])
+    }
+}
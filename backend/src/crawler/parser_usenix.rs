@@ -40,6 +40,14 @@ static RE_CONF: Lazy<Regex> =
 pub(super) struct UsenixParser;
 
 impl PaperParser for UsenixParser {
+    fn name(&self) -> &'static str {
+        "USENIX"
+    }
+
+    fn host_patterns(&self) -> &'static [&'static str] {
+        &["usenix.org"]
+    }
+
     fn parse_and_extract(
         &self,
         html_content: &str,
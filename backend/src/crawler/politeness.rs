@@ -0,0 +1,134 @@
+// src/crawler/politeness.rs
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use tokio::time::{Duration, Instant, sleep};
+use tracing;
+use url::Url;
+
+/// 同一ホストへの最小アクセス間隔
+const MIN_HOST_DELAY: Duration = Duration::from_secs(2);
+
+/// クローラーが名乗る User-Agent
+pub(super) const CRAWLER_USER_AGENT: &str = "PaperDeckBot/1.0 (+https://github.com/yujixr/PaperDeck)";
+
+/// ホストごとの最終アクセス時刻を記録し、最小アクセス間隔を守らせるレートリミッタ
+#[derive(Default)]
+pub(super) struct HostRateLimiter {
+    last_fetch: Mutex<HashMap<String, Instant>>,
+}
+
+impl HostRateLimiter {
+    pub(super) fn new() -> Self {
+        Self::default()
+    }
+
+    /// 同じホストへの直近のアクセスから `MIN_HOST_DELAY` 経過するまで待機する。
+    /// 複数タスクが同時に同じホストを待っていても、予約した時刻がずれるため
+    /// 互いに衝突しない。
+    pub(super) async fn wait_for_turn(&self, host: &str) {
+        let scheduled = {
+            let mut guard = self.last_fetch.lock().await;
+            let now = Instant::now();
+            let next_allowed = guard
+                .get(host)
+                .map(|&last| last + MIN_HOST_DELAY)
+                .unwrap_or(now);
+            let scheduled = next_allowed.max(now);
+            guard.insert(host.to_string(), scheduled);
+            scheduled
+        };
+
+        let now = Instant::now();
+        if scheduled > now {
+            sleep(scheduled - now).await;
+        }
+    }
+}
+
+/// シンプルな robots.txt パーサー兼キャッシュ
+/// (`User-agent: *` ブロックの `Disallow` のみをサポートする簡易実装)
+#[derive(Default)]
+pub(super) struct RobotsCache {
+    rules: Mutex<HashMap<String, Vec<String>>>,
+}
+
+impl RobotsCache {
+    pub(super) fn new() -> Self {
+        Self::default()
+    }
+
+    /// 指定したURLが robots.txt 上でクロール許可されているか確認する。
+    /// robots.txt の取得自体に失敗した場合は「許可」として扱う
+    /// (多くの実装が採用している、取得失敗時はブロックしない慣例に合わせる)。
+    pub(super) async fn is_allowed(&self, client: &reqwest::Client, url: &Url) -> bool {
+        let host = url.host_str().unwrap_or_default().to_string();
+
+        let disallowed_paths = {
+            let mut guard = self.rules.lock().await;
+            if let Some(rules) = guard.get(&host) {
+                rules.clone()
+            } else {
+                let rules = fetch_disallow_rules(client, url).await;
+                guard.insert(host.clone(), rules.clone());
+                rules
+            }
+        };
+
+        let path = url.path();
+        !disallowed_paths
+            .iter()
+            .any(|disallowed| !disallowed.is_empty() && path.starts_with(disallowed.as_str()))
+    }
+}
+
+/// robots.txt を取得し、`User-agent: *` ブロックの `Disallow` 一覧を返す
+async fn fetch_disallow_rules(client: &reqwest::Client, base_url: &Url) -> Vec<String> {
+    let mut robots_url = base_url.clone();
+    robots_url.set_path("/robots.txt");
+    robots_url.set_query(None);
+
+    let body = match client.get(robots_url.clone()).send().await {
+        Ok(resp) if resp.status().is_success() => resp.text().await.unwrap_or_default(),
+        Ok(resp) => {
+            tracing::debug!(
+                "No robots.txt (status {}) at {}, assuming allowed",
+                resp.status(),
+                robots_url
+            );
+            return Vec::new();
+        }
+        Err(e) => {
+            tracing::debug!("Failed to fetch robots.txt at {}: {}", robots_url, e);
+            return Vec::new();
+        }
+    };
+
+    parse_disallow_rules(&body)
+}
+
+/// `User-agent: *` ブロックの `Disallow` 行だけを抽出する簡易パーサー
+fn parse_disallow_rules(robots_txt: &str) -> Vec<String> {
+    let mut rules = Vec::new();
+    let mut in_wildcard_block = false;
+
+    for raw_line in robots_txt.lines() {
+        let line = raw_line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let Some((directive, value)) = line.split_once(':') else {
+            continue;
+        };
+        let directive = directive.trim().to_ascii_lowercase();
+        let value = value.trim();
+
+        match directive.as_str() {
+            "user-agent" => in_wildcard_block = value == "*",
+            "disallow" if in_wildcard_block => rules.push(value.to_string()),
+            _ => {}
+        }
+    }
+
+    rules
+}
@@ -0,0 +1,114 @@
+// src/error.rs
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use serde_json::json;
+use thiserror::Error;
+use tracing;
+
+use crate::crawler::CrawlError;
+use crate::webauthn::WebauthnCeremonyError;
+
+/// アプリケーション全体で使う統一エラー型
+///
+/// 各ハンドラはこの型を `Result<T, AppError>` の形で返し、
+/// `?` 演算子で個々のエラーを自然に変換できるようにします。
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error("Database error: {0}")]
+    Sqlx(sqlx::Error),
+
+    #[error("Username already taken")]
+    UserExists,
+
+    #[error("Incorrect username or password")]
+    InvalidCredentials,
+
+    #[error("Invalid or expired refresh token")]
+    InvalidRefreshToken,
+
+    #[error("{0} not found")]
+    NotFound(&'static str),
+
+    #[error("{0}")]
+    Validation(String),
+
+    #[error("Crawl error: {0}")]
+    Crawl(#[from] CrawlError),
+
+    #[error("WebAuthn error: {0}")]
+    Webauthn(#[from] WebauthnCeremonyError),
+
+    #[error("Internal server error")]
+    Internal(String),
+}
+
+/// JSON で返すエラーボディ
+#[derive(Debug, Serialize)]
+struct ErrorBody {
+    status: u16,
+    message: String,
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let (status, message) = match &self {
+            AppError::Sqlx(e) => {
+                tracing::error!("Database error: {}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Internal server error".to_string(),
+                )
+            }
+            AppError::UserExists => (StatusCode::CONFLICT, self.to_string()),
+            AppError::InvalidCredentials => (StatusCode::UNAUTHORIZED, self.to_string()),
+            AppError::InvalidRefreshToken => (StatusCode::UNAUTHORIZED, self.to_string()),
+            AppError::NotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            AppError::Validation(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
+            AppError::Crawl(e) => {
+                tracing::error!("Crawl error: {}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+            }
+            AppError::Webauthn(e) => {
+                // チャレンジ不一致や署名/カウンタの検証失敗はクライアント起因の
+                // ことが多いため、500ではなく400として返す
+                tracing::warn!("WebAuthn ceremony failed: {}", e);
+                (StatusCode::BAD_REQUEST, e.to_string())
+            }
+            AppError::Internal(msg) => {
+                tracing::error!("Internal error: {}", msg);
+                (StatusCode::INTERNAL_SERVER_ERROR, msg.clone())
+            }
+        };
+
+        let body = ErrorBody {
+            status: status.as_u16(),
+            message,
+        };
+
+        (status, Json(json!(body))).into_response()
+    }
+}
+
+/// `sqlx::Error` を検査し、`users` テーブルの一意制約違反であれば
+/// 自動的に `UserExists` (409) に変換します。
+/// (SQLite のドライバの `message()` は `UNIQUE constraint failed:
+/// users.username` のように対象のテーブル名・カラム名を含むため、
+/// それを見て `users` テーブル由来かどうかを判定します。
+/// `webauthn_credentials.credential_id` など他テーブルの一意制約違反まで
+/// `UserExists` にしてしまうと誤ったメッセージを返すため、それ以外は
+/// `Sqlx` (500) に落とします。)
+impl From<sqlx::Error> for AppError {
+    fn from(err: sqlx::Error) -> Self {
+        if let Some(db_err) = err.as_database_error() {
+            if db_err.is_unique_violation() && db_err.message().contains("users.") {
+                return AppError::UserExists;
+            }
+        }
+        AppError::Sqlx(err)
+    }
+}
+
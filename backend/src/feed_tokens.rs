@@ -0,0 +1,51 @@
+// src/feed_tokens.rs
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use sqlx::{Pool, Sqlite};
+
+/// 生成するフィードトークンのバイト長 (Base64URLエンコード後は43文字程度になる)
+const TOKEN_BYTES: usize = 32;
+
+fn generate_raw_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// ユーザーのフィードトークンを取得する。まだ発行されていなければ新規に発行する。
+///
+/// リフレッシュトークンと違い、フィードリーダーのURLに埋め込んで繰り返し
+/// 使われるcapability URL方式の資格情報のため、ハッシュ化はせずそのままDBに
+/// 保存する (ユーザーに再表示できる必要があるため)。漏洩時の影響範囲も
+/// 「いいねした論文の閲覧」のみに限られる低リスクな値であることを踏まえての判断。
+pub async fn get_or_create(db_pool: &Pool<Sqlite>, user_id: i64) -> Result<String, sqlx::Error> {
+    let existing: Option<String> =
+        sqlx::query_scalar("SELECT feed_token FROM users WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_one(db_pool)
+            .await?;
+
+    if let Some(token) = existing {
+        return Ok(token);
+    }
+
+    let token = generate_raw_token();
+    sqlx::query("UPDATE users SET feed_token = ? WHERE user_id = ?")
+        .bind(&token)
+        .bind(user_id)
+        .execute(db_pool)
+        .await?;
+
+    Ok(token)
+}
+
+/// フィードトークンからユーザーIDを引く (Atomフィード配信時の認証に使用)
+pub async fn user_id_for_token(
+    db_pool: &Pool<Sqlite>,
+    token: &str,
+) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar("SELECT user_id FROM users WHERE feed_token = ?")
+        .bind(token)
+        .fetch_optional(db_pool)
+        .await
+}
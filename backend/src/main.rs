@@ -5,13 +5,25 @@ use tower_http::cors::{Any, CorsLayer};
 use tracing_subscriber::{EnvFilter, fmt};
 
 mod auth;
+mod config;
+mod crawl_jobs;
 mod crawler;
+mod error;
+mod feed_tokens;
 mod models;
+mod recommend;
+mod refresh_tokens;
 mod routes;
 mod state;
+mod webauthn;
 
 use crate::auth::Keys;
+use crate::config::Config;
+use crate::crawl_jobs::CrawlJobQueue;
+use crate::recommend::TfIdfIndex;
+use crate::webauthn::WebauthnService;
 use state::AppState;
+use std::sync::Arc;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -20,10 +32,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
     fmt::Subscriber::builder().with_env_filter(filter).init();
 
-    let db_url =
-        std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:./papers.sqlite".to_string());
-    let connect_options = SqliteConnectOptions::from_str(&db_url)?.create_if_missing(true);
-    tracing::info!("Connecting to database: {}", db_url);
+    // 設定を一度だけ読み込み、検証する
+    let config = Config::init();
+
+    let connect_options =
+        SqliteConnectOptions::from_str(&config.database_url)?.create_if_missing(true);
+    tracing::info!("Connecting to database: {}", config.database_url);
 
     let db_pool = SqlitePoolOptions::new()
         .connect_with(connect_options)
@@ -31,12 +45,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     sqlx::migrate!("./migrations").run(&db_pool).await?;
 
-    let jwt_secret = std::env::var("JWT_SECRET")
-        .unwrap_or_else(|_| "a_very_secret_and_long_key_please_change_me".to_string());
-    let keys = Keys::new(jwt_secret.as_bytes());
-    let app_state = AppState { db_pool, keys };
+    let keys = Keys::new(config.jwt_secret.as_bytes());
+    let webauthn = WebauthnService::new(&config.webauthn_rp_id, &config.webauthn_origin);
+    let crawl_jobs = CrawlJobQueue::spawn(db_pool.clone());
+    let static_dir = config.static_dir.clone();
+    let port = config.port;
+
+    // 推薦用のTF-IDFインデックスは起動時に一度だけ構築し、以後はメモリ上のものを使い回す
+    // (クロールで新しい論文が追加されても、反映されるのは次回の再起動時)
+    let recommender = Arc::new(TfIdfIndex::build(&db_pool).await?);
+
+    let app_state = AppState {
+        db_pool,
+        keys,
+        config,
+        webauthn,
+        crawl_jobs,
+        recommender,
+    };
 
-    let static_dir = std::env::var("STATIC_DIR").unwrap_or_else(|_| "../frontend/dist".to_string());
     tracing::info!("Serving static files from: {}", static_dir);
 
     let cors = CorsLayer::new()
@@ -47,7 +74,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let app = routes::create_router(app_state, static_dir).layer(cors);
 
     // サーバーの起動
-    let port = std::env::var("PORT").unwrap_or_else(|_| "3000".to_string());
     let addr_str = format!("0.0.0.0:{}", port);
     let addr: SocketAddr = addr_str.parse().expect("Failed to parse address and port");
     tracing::info!("🚀 Server listening on {}", addr);
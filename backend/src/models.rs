@@ -11,6 +11,15 @@ pub enum PaperStatus {
     Read,
 }
 
+// ユーザーの権限 (DBとJWTクレーム用)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "role_enum", rename_all = "PascalCase")]
+#[serde(rename_all = "PascalCase")]
+pub enum Role {
+    User,
+    Admin,
+}
+
 // 2. Paper 構造体 (DBからの読み取り用)
 #[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
 pub struct Paper {
@@ -37,13 +46,17 @@ pub struct StatusPayload {
 }
 
 // DBから読み取る User 構造体
+//
+// `password_hash` はパスキーのみで登録したユーザーでは存在しないため
+// `Option` になっています。
 #[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
 pub struct User {
     pub user_id: i64,
     pub username: String,
     #[serde(skip)] // パスワードハッシュはAPIで返さない
     #[schema(hidden = true)] // Utoipa スキーマからも除外
-    pub password_hash: String,
+    pub password_hash: Option<String>,
+    pub role: Role,
 }
 
 // ユーザー登録 (POST /auth/register) のペイロード
@@ -65,6 +78,18 @@ pub struct LoginPayload {
 pub struct AuthToken {
     pub token: String,
     pub token_type: String, // "Bearer"
+    /// 新しいアクセストークンと交換するためのリフレッシュトークン。
+    /// Cookie非対応のクライアント (モバイルアプリ等) 向けにJSONでも返す。
+    pub refresh_token: String,
+}
+
+// リフレッシュ (POST /auth/refresh) / ログアウト (POST /auth/logout) のペイロード
+//
+// Cookieでリフレッシュトークンを送るブラウザと、ボディで送るその他の
+// クライアントの両方に対応するため、ボディは任意とする。
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RefreshPayload {
+    pub refresh_token: Option<String>,
 }
 
 // クローリング (POST /admin/trigger_crawl) のペイロード
@@ -76,12 +101,135 @@ pub struct CrawlPayload {
 // クローリング (POST /admin/trigger_crawl) のレスポンス
 #[derive(Debug, Serialize, ToSchema)]
 pub struct CrawlResponse {
+    pub job_id: i64,
     pub message: String,
 }
 
+// クロールジョブの状態
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "crawl_job_status_enum", rename_all = "PascalCase")]
+#[serde(rename_all = "PascalCase")]
+pub enum CrawlJobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+// クロールジョブ (DBからの読み取り用)
+// (GET /admin/crawl_jobs, GET /admin/crawl_jobs/{id} のレスポンス)
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct CrawlJob {
+    pub id: i64,
+    pub submitted_by: i64,
+    pub status: CrawlJobStatus,
+    /// JSON配列としてシリアライズされたURLリスト (そのまま文字列で返す)
+    pub urls: String,
+    pub processed_urls: i64,
+    pub papers_found: i64,
+    pub papers_inserted: i64,
+    pub summary: Option<String>,
+    pub created_at: String,
+    pub finished_at: Option<String>,
+}
+
+// 学会・年度ごとの閲覧状況 (GET /papers/stats の内訳)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConferenceStats {
+    pub conference_name: String,
+    pub year: i64,
+    /// コーパス中にある論文数
+    pub available: i64,
+    /// 評価済み (いいね/既読のいずれか) の論文数
+    pub rated: i64,
+    /// いいねした論文数
+    pub liked: i64,
+    /// rated / available のパーセンテージ (小数第1位まで)
+    pub coverage_percent: f64,
+}
+
+// 閲覧状況の集計 (GET /papers/stats) のレスポンス
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StatsResponse {
+    pub total_available: i64,
+    pub total_rated: i64,
+    pub total_liked: i64,
+    pub by_conference: Vec<ConferenceStats>,
+}
+
+// フィードトークン取得 (GET /papers/liked/feed_token) のレスポンス
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeedTokenResponse {
+    /// `GET /papers/liked/feed.atom?token=...` に埋め込んで使う個人用トークン
+    pub token: String,
+}
+
 // 論文取得 (GET /papers/next) のクエリパラメータ
 #[derive(Debug, Deserialize)]
 pub struct NextPaperParams {
     pub conference: Option<String>,
     pub year: Option<i64>,
+    /// "relevant" を指定すると、いいねした論文と似た論文を優先して返す。
+    /// 未指定、またはそれ以外の値の場合 (および似た論文が見つからない場合) は、
+    /// `q` があれば bm25 順、なければ従来通りランダムに選ぶ。
+    pub mode: Option<String>,
+    /// 指定すると `papers_fts` で全文検索した論文に絞り込む。`mode=relevant` で
+    /// 似た論文が選ばれた場合を除き、bm25 順 (最も関連度が高いもの) を返す。
+    /// 未指定の場合は従来通りランダムに選ぶ。
+    pub q: Option<String>,
+}
+
+// 論文全文検索 (GET /papers/search) のクエリパラメータ
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    /// `title` / `authors` / `abstract_text` を対象としたFTS5の検索語
+    pub q: String,
+    pub conference: Option<String>,
+    pub year: Option<i64>,
+}
+
+// --- WebAuthn (パスキー) ---
+
+// パスキー登録開始 (POST /auth/webauthn/register/start) のペイロード
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WebauthnRegisterStartPayload {
+    pub username: String,
+}
+
+// パスキーログイン開始 (POST /auth/webauthn/login/start) のペイロード
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WebauthnLoginStartPayload {
+    pub username: String,
+}
+
+// セレモニー開始時に返す、チャレンジIDとブラウザに渡すWebAuthnオプション
+//
+// `options` は `webauthn-rs` が生成する `CreationChallengeResponse` /
+// `RequestChallengeResponse` をそのままJSON化したもので、ブラウザの
+// `navigator.credentials.create()` / `.get()` にほぼそのまま渡せます。
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebauthnChallenge {
+    pub challenge_id: String,
+    #[schema(value_type = Object)]
+    pub options: serde_json::Value,
+}
+
+// パスキー登録完了 (POST /auth/webauthn/register/finish) のペイロード
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WebauthnRegisterFinishPayload {
+    pub challenge_id: String,
+    /// ブラウザの `navigator.credentials.create()` が返す `PublicKeyCredential`
+    /// (`webauthn-rs` の `RegisterPublicKeyCredential` としてデシリアライズされます)
+    #[schema(value_type = Object)]
+    pub credential: serde_json::Value,
+}
+
+// パスキーログイン完了 (POST /auth/webauthn/login/finish) のペイロード
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WebauthnLoginFinishPayload {
+    pub challenge_id: String,
+    /// ブラウザの `navigator.credentials.get()` が返す `PublicKeyCredential`
+    /// (`webauthn-rs` の `PublicKeyCredential` としてデシリアライズされます)
+    #[schema(value_type = Object)]
+    pub credential: serde_json::Value,
 }
@@ -0,0 +1,149 @@
+// src/recommend.rs
+//
+// 「いいね」した論文と似たトピックの論文を推薦するための、軽量なTF-IDFベースの
+// 類似度検索。外部MLサービスに依存せず、起動時に一度だけ全論文からベクトル空間を
+// 構築し、以降はメモリ上の値を参照するだけにすることでレイテンシを抑える。
+
+use once_cell::sync::Lazy;
+use sqlx::{Pool, Sqlite};
+use std::collections::{HashMap, HashSet};
+
+/// トークン化で除外するごく basic な英語のストップワード
+static STOPWORDS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+    [
+        "a", "an", "the", "and", "or", "but", "of", "in", "on", "at", "to", "for", "with", "by",
+        "is", "are", "was", "were", "be", "been", "being", "this", "that", "these", "those", "it",
+        "as", "from", "we", "our", "their", "its", "using", "based", "via", "we",
+    ]
+    .into_iter()
+    .collect()
+});
+
+/// 最大候補スキャン件数 (レイテンシを抑えるためのサンプリング上限)
+const MAX_CANDIDATES: i64 = 300;
+
+/// 小文字化し、英数字以外で分割してストップワードを除いたトークン列を返す
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|s| s.len() > 1 && !STOPWORDS.contains(s))
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// 1論文分のスパースなTF-IDFベクトル (L2正規化済み)
+type SparseVector = HashMap<String, f64>;
+
+fn normalize(mut vector: SparseVector) -> SparseVector {
+    let norm = vector.values().map(|v| v * v).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        for value in vector.values_mut() {
+            *value /= norm;
+        }
+    }
+    vector
+}
+
+fn cosine_similarity(a: &SparseVector, b: &SparseVector) -> f64 {
+    // 疎ベクトル同士の内積は、小さい方を基準に走査すると速い
+    let (smaller, larger) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    smaller
+        .iter()
+        .map(|(term, weight)| weight * larger.get(term).copied().unwrap_or(0.0))
+        .sum()
+}
+
+/// 起動時に一度だけ構築する、全論文のTF-IDFベクトル空間
+///
+/// `AppState` にキャッシュして保持し、`GET /papers/next?mode=relevant` から
+/// 参照する。クロールで新しい論文が追加されても再構築はしない
+/// (次回のサーバー再起動時に反映される、という割り切り)。
+pub struct TfIdfIndex {
+    vectors: HashMap<i64, SparseVector>,
+}
+
+impl TfIdfIndex {
+    /// DB上の全論文からTF-IDFベクトル空間を構築する
+    pub async fn build(db_pool: &Pool<Sqlite>) -> Result<Self, sqlx::Error> {
+        let rows: Vec<(i64, String, Option<String>)> =
+            sqlx::query_as("SELECT id, title, abstract_text FROM papers").fetch_all(db_pool).await?;
+
+        let doc_count = rows.len() as f64;
+        let mut term_doc_frequency: HashMap<String, f64> = HashMap::new();
+        let mut doc_term_frequencies: HashMap<i64, HashMap<String, f64>> = HashMap::new();
+
+        for (id, title, abstract_text) in &rows {
+            let text = format!("{} {}", title, abstract_text.as_deref().unwrap_or(""));
+            let tokens = tokenize(&text);
+
+            let mut term_frequency: HashMap<String, f64> = HashMap::new();
+            for token in tokens {
+                *term_frequency.entry(token).or_insert(0.0) += 1.0;
+            }
+            for term in term_frequency.keys() {
+                *term_doc_frequency.entry(term.clone()).or_insert(0.0) += 1.0;
+            }
+            doc_term_frequencies.insert(*id, term_frequency);
+        }
+
+        let mut vectors = HashMap::with_capacity(doc_term_frequencies.len());
+        for (id, term_frequency) in doc_term_frequencies {
+            let mut vector: SparseVector = HashMap::with_capacity(term_frequency.len());
+            for (term, tf) in term_frequency {
+                let df = term_doc_frequency.get(&term).copied().unwrap_or(1.0);
+                // df はこの論文自身に出現する語なので常に1以上。全論文に出現する語の
+                // 重みが0以下にならないよう log の引数を (N/df) + 1 にしている
+                let idf = ((doc_count / df) + 1.0).ln();
+                vector.insert(term, tf * idf);
+            }
+            vectors.insert(id, normalize(vector));
+        }
+
+        Ok(Self { vectors })
+    }
+
+    /// 「いいね」した論文群の平均ベクトルとして、ユーザーの興味プロファイルを構築する
+    /// (インデックス構築後に追加された論文は含まれないため無視される)
+    fn profile_vector(&self, liked_paper_ids: &[i64]) -> Option<SparseVector> {
+        let liked_vectors: Vec<&SparseVector> = liked_paper_ids
+            .iter()
+            .filter_map(|id| self.vectors.get(id))
+            .collect();
+
+        if liked_vectors.is_empty() {
+            return None;
+        }
+
+        let mut profile: SparseVector = HashMap::new();
+        for vector in &liked_vectors {
+            for (term, weight) in vector.iter() {
+                *profile.entry(term.clone()).or_insert(0.0) += weight;
+            }
+        }
+        let count = liked_vectors.len() as f64;
+        for weight in profile.values_mut() {
+            *weight /= count;
+        }
+        Some(profile)
+    }
+
+    /// `liked_paper_ids` から興味プロファイルを構築し、`candidate_ids` の中から
+    /// コサイン類似度が最も高い論文のIDを返す。
+    ///
+    /// プロファイルを構築できない (いいねが1件もない、あるいはインデックス構築後に
+    /// 登録された論文しかいいねしていない) 場合は `None` を返し、呼び出し元は
+    /// 既存のランダム選択にフォールバックする。
+    pub fn most_relevant(&self, liked_paper_ids: &[i64], candidate_ids: &[i64]) -> Option<i64> {
+        let profile = self.profile_vector(liked_paper_ids)?;
+
+        candidate_ids
+            .iter()
+            .filter_map(|id| self.vectors.get(id).map(|vector| (*id, vector)))
+            .map(|(id, vector)| (id, cosine_similarity(&profile, vector)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(id, _)| id)
+    }
+}
+
+/// 候補スキャンの上限件数 (`papers.rs` から参照)
+pub const MAX_CANDIDATE_SCAN: i64 = MAX_CANDIDATES;
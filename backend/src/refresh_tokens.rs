@@ -0,0 +1,130 @@
+// src/refresh_tokens.rs
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::{Pool, Sqlite};
+use tracing;
+
+use crate::error::AppError;
+
+/// 生成する生トークンのバイト長 (Base64URLエンコード後は43文字程度になる)
+const TOKEN_BYTES: usize = 32;
+
+/// 高エントロピーなランダムトークンをハッシュ化する。
+///
+/// パスワードと違い、総当たり対象となる低エントロピーな秘密ではないため
+/// Argon2のような低速ハッシュは不要で、高速な SHA-256 で十分かつ適切。
+fn hash_token(raw: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(raw.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn generate_raw_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// 新規のリフレッシュトークンを発行し、ハッシュ化してDBに保存する。
+/// 生のトークン (DBには保存されない) を呼び出し元に返す。
+pub async fn issue(
+    db_pool: &Pool<Sqlite>,
+    user_id: i64,
+    expires_in_days: i64,
+) -> Result<String, AppError> {
+    let raw_token = generate_raw_token();
+    let token_hash = hash_token(&raw_token);
+    let expires_at = (Utc::now() + Duration::days(expires_in_days)).to_rfc3339();
+
+    sqlx::query("INSERT INTO refresh_tokens (token_hash, user_id, expires_at) VALUES (?, ?, ?)")
+        .bind(&token_hash)
+        .bind(user_id)
+        .bind(&expires_at)
+        .execute(db_pool)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(raw_token)
+}
+
+/// リフレッシュトークンを検証し、使い捨てとして失効させたうえで
+/// 新しいリフレッシュトークンを発行する (ローテーション)。
+///
+/// 既に失効済み (ローテーション済み、またはログアウト済み) のトークンが
+/// 再提示された場合は盗難の兆候とみなし、そのユーザーの全リフレッシュ
+/// トークンを失効させてセッション全体を終了させる。
+pub async fn verify_and_rotate(
+    db_pool: &Pool<Sqlite>,
+    presented_token: &str,
+    expires_in_days: i64,
+) -> Result<(i64, String), AppError> {
+    let token_hash = hash_token(presented_token);
+
+    let row: Option<(i64, String, Option<String>)> = sqlx::query_as(
+        "SELECT user_id, expires_at, revoked_at FROM refresh_tokens WHERE token_hash = ?",
+    )
+    .bind(&token_hash)
+    .fetch_optional(db_pool)
+    .await?;
+
+    let Some((user_id, expires_at, revoked_at)) = row else {
+        tracing::warn!("Refresh token rejected (not recognized)");
+        return Err(AppError::InvalidRefreshToken);
+    };
+
+    if revoked_at.is_some() {
+        tracing::warn!(
+            "Reuse of an already-revoked refresh token detected for user {}; revoking all of their sessions",
+            user_id
+        );
+        revoke_all_for_user(db_pool, user_id).await?;
+        return Err(AppError::InvalidRefreshToken);
+    }
+
+    let is_expired = DateTime::parse_from_rfc3339(&expires_at)
+        .map(|dt| dt.with_timezone(&Utc) < Utc::now())
+        .unwrap_or(true); // パース不能な値は安全側に倒して期限切れ扱いにする
+    if is_expired {
+        tracing::warn!("Refresh token for user {} has expired", user_id);
+        return Err(AppError::InvalidRefreshToken);
+    }
+
+    // ローテーション: 新しいトークンを発行してから、古いものを失効させる
+    let new_raw_token = issue(db_pool, user_id, expires_in_days).await?;
+
+    sqlx::query("UPDATE refresh_tokens SET revoked_at = datetime('now') WHERE token_hash = ?")
+        .bind(&token_hash)
+        .execute(db_pool)
+        .await?;
+
+    Ok((user_id, new_raw_token))
+}
+
+/// 指定されたリフレッシュトークンを失効させる (ログアウト)。
+/// 未知のトークンが渡されても冪等に成功扱いとする。
+pub async fn revoke(db_pool: &Pool<Sqlite>, presented_token: &str) -> Result<(), AppError> {
+    let token_hash = hash_token(presented_token);
+    sqlx::query(
+        "UPDATE refresh_tokens SET revoked_at = datetime('now')
+         WHERE token_hash = ? AND revoked_at IS NULL",
+    )
+    .bind(&token_hash)
+    .execute(db_pool)
+    .await
+    .map_err(AppError::from)?;
+    Ok(())
+}
+
+async fn revoke_all_for_user(db_pool: &Pool<Sqlite>, user_id: i64) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE refresh_tokens SET revoked_at = datetime('now')
+         WHERE user_id = ? AND revoked_at IS NULL",
+    )
+    .bind(user_id)
+    .execute(db_pool)
+    .await
+    .map_err(AppError::from)?;
+    Ok(())
+}
@@ -1,22 +1,46 @@
 // api/src/routes/admin.rs
-use axum::{Json, Router, extract::State, http::StatusCode, response::IntoResponse, routing::post};
-use tokio;
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::{get, post},
+};
 use tracing;
 
 use crate::state::AppState;
 // AuthUser を Extension で受け取れるようにする
 // (AuthUser は `auth.rs` で pub になっている必要があります)
-use crate::auth::AuthUser;
+use crate::auth::{AuthUser, RequireScope, require_scope};
+use crate::error::AppError;
 use axum::Extension;
 
-use crate::models::{CrawlPayload, CrawlResponse};
+use crate::models::{CrawlJob, CrawlPayload, CrawlResponse};
 
 /// 管理用ルート (/admin/...) を構築します
-pub fn create_admin_routes() -> Router<AppState> {
-    Router::new().route("/admin/trigger_crawl", post(trigger_crawl))
+///
+/// どのエンドポイントもクローリングという重い処理の起動・閲覧に関わるため、
+/// `"admin:crawl"` スコープを要求する `require_scope` ミドルウェアで保護します。
+/// (このレイヤーは `auth_middleware` の内側で動くよう、呼び出し側で
+/// 外側にもう一段 `auth_middleware` を重ねる前提です)
+pub fn create_admin_routes(app_state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/admin/trigger_crawl", post(trigger_crawl))
+        .route("/admin/crawl_jobs", get(list_crawl_jobs))
+        .route("/admin/crawl_jobs/:id", get(get_crawl_job))
+        .layer(middleware::from_fn_with_state(
+            RequireScope::new(app_state, "admin:crawl"),
+            require_scope,
+        ))
 }
 
-/// クローリングをバックグラウンドで実行する (POST /admin/trigger_crawl)
+/// クロールジョブをキューに投入する (POST /admin/trigger_crawl)
+///
+/// 以前はここで `tokio::spawn` して結果を破棄するだけでしたが、いまは
+/// `crawl_jobs` テーブルに `Queued` な行を作り、単一のバックグラウンド
+/// ワーカー (`crawl_jobs::CrawlJobQueue`) に処理を任せます。進捗や結果は
+/// `GET /admin/crawl_jobs/{id}` から後で確認できます。
 #[utoipa::path(
     post,
     path = "/api/admin/trigger_crawl",
@@ -34,9 +58,9 @@ pub fn create_admin_routes() -> Router<AppState> {
     responses(
         (
             status = 202,
-            description = "クローリング開始",
+            description = "クロールジョブをキューに投入",
             body = CrawlResponse,
-            example = json!({"message": "Crawl started in background."})
+            example = json!({"job_id": 1, "message": "Crawl job queued."})
         ),
         (status = 500, description = "サーバーエラー")
     ),
@@ -45,37 +69,86 @@ pub fn create_admin_routes() -> Router<AppState> {
 async fn trigger_crawl(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
-    // JSONペイロードを受け取る
     Json(payload): Json<CrawlPayload>,
-) -> impl IntoResponse {
+) -> Result<impl IntoResponse, AppError> {
     tracing::info!("Crawl triggered by user_id: {}", auth_user.user_id);
 
-    // クローリングは時間がかかるため、HTTPリクエストをブロックしないよう
-    // `tokio::spawn` を使ってバックグラウンドタスクとして実行します。
-    // DBプール (AppState) は `Clone` 可能です。
-    let db_pool = state.db_pool.clone();
+    let urls_json = serde_json::to_string(&payload.urls)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize URL list: {}", e)))?;
 
-    // ペイロードからURLリストを取得
-    let urls_to_crawl = payload.urls;
+    let db_result = sqlx::query(
+        "INSERT INTO crawl_jobs (submitted_by, urls) VALUES (?, ?)",
+    )
+    .bind(auth_user.user_id)
+    .bind(&urls_json)
+    .execute(&state.db_pool)
+    .await
+    .map_err(AppError::from)?;
 
-    tokio::spawn(async move {
-        tracing::info!("Background crawl task started...");
-        // クローラー関数にURLリストを渡す
-        match crate::crawler::run_crawl(&db_pool, urls_to_crawl).await {
-            Ok(summary) => {
-                tracing::info!("Background crawl finished: {}", summary);
-            }
-            Err(e) => {
-                tracing::error!("Background crawl failed: {}", e);
-            }
-        }
-    });
+    let job_id = db_result.last_insert_rowid();
+    state.crawl_jobs.enqueue(job_id);
 
-    // リクエストにはすぐに「受け付けた」というレスポンスを返します
-    (
+    Ok((
         StatusCode::ACCEPTED,
         Json(CrawlResponse {
-            message: "Crawl started in background.".to_string(),
+            job_id,
+            message: "Crawl job queued.".to_string(),
         }),
+    ))
+}
+
+/// 最近のクロールジョブ一覧を取得する (GET /admin/crawl_jobs)
+#[utoipa::path(
+    get,
+    path = "/api/admin/crawl_jobs",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "直近のクロールジョブ一覧 (新しい順)", body = Vec<CrawlJob>),
+        (status = 500, description = "サーバーエラー")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn list_crawl_jobs(State(state): State<AppState>) -> Result<Json<Vec<CrawlJob>>, AppError> {
+    let jobs = sqlx::query_as::<_, CrawlJob>(
+        "SELECT id, submitted_by, status, urls, processed_urls, papers_found,
+                papers_inserted, summary, created_at, finished_at
+         FROM crawl_jobs
+         ORDER BY created_at DESC
+         LIMIT 50",
     )
+    .fetch_all(&state.db_pool)
+    .await?;
+
+    Ok(Json(jobs))
+}
+
+/// 1件のクロールジョブの詳細を取得する (GET /admin/crawl_jobs/{id})
+#[utoipa::path(
+    get,
+    path = "/api/admin/crawl_jobs/{id}",
+    tag = "Admin",
+    params(("id" = i64, Path, description = "クロールジョブID")),
+    responses(
+        (status = 200, description = "クロールジョブの詳細", body = CrawlJob),
+        (status = 404, description = "ジョブが見つからない"),
+        (status = 500, description = "サーバーエラー")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_crawl_job(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<CrawlJob>, AppError> {
+    let job = sqlx::query_as::<_, CrawlJob>(
+        "SELECT id, submitted_by, status, urls, processed_urls, papers_found,
+                papers_inserted, summary, created_at, finished_at
+         FROM crawl_jobs
+         WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_optional(&state.db_pool)
+    .await?
+    .ok_or(AppError::NotFound("Crawl job"))?;
+
+    Ok(Json(job))
 }
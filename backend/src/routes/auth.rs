@@ -1,16 +1,29 @@
 // src/routes/auth.rs
-use axum::{Extension, Json, Router, extract::State, http::StatusCode, routing::{get, post}};
+use axum::{Extension, Json, Router, extract::State, routing::{get, post}};
+use axum_extra::extract::cookie::CookieJar;
 use tracing;
 
-use crate::auth::{AuthUser, create_jwt, hash_password, validate_registration, verify_password};
-use crate::models::{AuthToken, LoginPayload, RegisterPayload, User};
+use crate::auth::{
+    AuthUser, REFRESH_COOKIE_NAME, build_auth_cookie, build_csrf_cookie, build_expired_auth_cookie,
+    build_expired_csrf_cookie, build_expired_refresh_cookie, build_refresh_cookie, create_jwt,
+    generate_csrf_token, hash_password, validate_registration, verify_password,
+};
+use crate::error::AppError;
+use crate::models::{AuthToken, LoginPayload, RefreshPayload, RegisterPayload, Role, User};
+use crate::refresh_tokens;
 use crate::state::AppState;
 
 /// 認証ルート (公開) (/auth/...) を構築します
+///
+/// `/auth/refresh` は有効なアクセストークンを前提にできない
+/// (アクセストークンの期限切れを回復するためのエンドポイントなので)
+/// ため、あえて未認証のルートとしています。
 pub fn create_public_auth_routes() -> Router<AppState> {
     Router::new()
         .route("/auth/login", post(login))
         .route("/auth/register", post(register))
+        .route("/auth/refresh", post(refresh))
+        .route("/auth/logout", post(logout))
 }
 
 /// 認証ルート (保護) (/auth/...) を構築します
@@ -46,35 +59,22 @@ async fn get_me(
     State(state): State<AppState>,
     // auth_middleware が添付したユーザー情報を Extension で受け取る
     Extension(auth_user): Extension<AuthUser>,
-) -> Result<Json<User>, (StatusCode, String)> {
+) -> Result<Json<User>, AppError> {
     let user_id = auth_user.user_id;
 
     // ミドルウェアで存在チェックはしていますが、
     // ここで完全なユーザー情報を取得します
-    let user = match sqlx::query_as::<_, User>(
-        "SELECT user_id, username, password_hash FROM users WHERE user_id = ?",
+    let user = sqlx::query_as::<_, User>(
+        "SELECT user_id, username, password_hash, role FROM users WHERE user_id = ?",
     )
     .bind(user_id)
     .fetch_optional(&state.db_pool)
-    .await
-    {
-        Ok(Some(user)) => user,
-        Ok(None) => {
-            // ミドルウェアを通過したのにユーザーがいない (ほぼあり得ないが安全のため)
-            tracing::warn!("User not found for ID {} (from valid token)", user_id);
-            return Err((
-                StatusCode::NOT_FOUND,
-                "User associated with token not found".to_string(),
-            ));
-        }
-        Err(e) => {
-            tracing::error!("Database error in get_me for user {}: {}", user_id, e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Database error: {}", e),
-            ));
-        }
-    };
+    .await?
+    .ok_or_else(|| {
+        // ミドルウェアを通過したのにユーザーがいない (ほぼあり得ないが安全のため)
+        tracing::warn!("User not found for ID {} (from valid token)", user_id);
+        AppError::NotFound("User associated with token")
+    })?;
 
     // User 構造体は password_hash に #[serde(skip)] が付いているため、
     // Json(user) でシリアライズしてもハッシュは含まれません。
@@ -115,40 +115,32 @@ async fn get_me(
 )]
 async fn login(
     State(state): State<AppState>,
+    jar: CookieJar,
     Json(payload): Json<LoginPayload>,
-) -> Result<Json<AuthToken>, (StatusCode, String)> {
+) -> Result<(CookieJar, Json<AuthToken>), AppError> {
     // 1. ユーザー名でDBを検索
-    let user = match sqlx::query_as::<_, User>(
-        "SELECT user_id, username, password_hash FROM users WHERE username = ?",
+    let user = sqlx::query_as::<_, User>(
+        "SELECT user_id, username, password_hash, role FROM users WHERE username = ?",
     )
     .bind(&payload.username)
     .fetch_optional(&state.db_pool)
-    .await
-    {
-        Ok(Some(user)) => user,
-        Ok(None) => {
-            tracing::warn!("Login failed (user not found): {}", payload.username);
-            return Err((
-                StatusCode::UNAUTHORIZED,
-                "Incorrect username or password".to_string(),
-            ));
-        }
-        Err(e) => {
-            tracing::error!(
-                "Database error during login for {}: {}",
-                payload.username,
-                e
-            );
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Database error: {}", e),
-            ));
-        }
-    };
+    .await?
+    .ok_or_else(|| {
+        tracing::warn!("Login failed (user not found): {}", payload.username);
+        AppError::InvalidCredentials
+    })?;
 
     // 2. パスワードハッシュを検証 (ブロッキングタスクとして実行)
+    // パスキーのみで登録したユーザーは password_hash を持たないため、
+    // その場合はパスワードログイン自体を拒否する
+    let Some(password_hash) = user.password_hash.clone() else {
+        tracing::warn!(
+            "Login failed (passkey-only account, no password set): {}",
+            user.username
+        );
+        return Err(AppError::InvalidCredentials);
+    };
     let password = payload.password; // クロージャに move するため
-    let password_hash = user.password_hash.clone(); // 同上
     let username_for_log = user.username.clone(); // ログ用
 
     let is_valid = tokio::task::spawn_blocking(move || verify_password(&password, &password_hash))
@@ -156,37 +148,43 @@ async fn login(
         .map_err(|e| {
             // タスクの JoinError (例: パニック)
             tracing::error!("spawn_blocking failed for verify_password: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
+            AppError::Internal("Internal server error".to_string())
         })?; // -> bool
 
     if !is_valid {
         tracing::warn!("Login failed (invalid password): {}", username_for_log);
-        return Err((
-            StatusCode::UNAUTHORIZED,
-            "Incorrect username or password".to_string(),
-        ));
+        return Err(AppError::InvalidCredentials);
     }
 
-    // 3. JWTを生成 (authモジュールから呼び出し)
-    let token = match create_jwt(user.user_id, &state.keys) {
-        Ok(token) => token,
-        Err(e) => {
-            tracing::error!("Failed to generate JWT for user {}: {}", user.user_id, e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to generate token".to_string(),
-            ));
-        }
-    };
+    // 3. JWTとリフレッシュトークンを生成 (authモジュールから呼び出し)
+    let token = create_jwt(user.user_id, user.role, &state.keys, state.config.jwt_expires_in).map_err(|e| {
+        tracing::error!("Failed to generate JWT for user {}: {}", user.user_id, e);
+        AppError::Internal("Failed to generate token".to_string())
+    })?;
+    let refresh_token = refresh_tokens::issue(
+        &state.db_pool,
+        user.user_id,
+        state.config.refresh_token_expires_in_days,
+    )
+    .await?;
 
     tracing::info!("User logged in: {}", user.username);
-    Ok(Json(AuthToken {
-        token,
-        token_type: "Bearer".to_string(),
-    }))
+
+    // Bearer方式のクライアント向けにJSONボディでも返しつつ、
+    // ブラウザのSPA向けにHttpOnly Cookieとしても設定する
+    let session_max_age = state.config.refresh_token_expires_in_days * 24 * 60 * 60;
+    let jar = jar
+        .add(build_auth_cookie(token.clone(), state.config.jwt_maxage))
+        .add(build_refresh_cookie(refresh_token.clone(), session_max_age))
+        .add(build_csrf_cookie(generate_csrf_token(), session_max_age));
+    Ok((
+        jar,
+        Json(AuthToken {
+            token,
+            token_type: "Bearer".to_string(),
+            refresh_token,
+        }),
+    ))
 }
 
 /// ユーザー登録 (POST /auth/register)
@@ -229,13 +227,12 @@ async fn login(
 )]
 async fn register(
     State(state): State<AppState>,
+    jar: CookieJar,
     Json(payload): Json<RegisterPayload>,
-) -> Result<Json<User>, (StatusCode, String)> {
+) -> Result<(CookieJar, Json<User>), AppError> {
     // 1. ユーザー名とパスワードのバリデーション
-    if let Err(msg) = validate_registration(&payload.username, &payload.password) {
-        // エラーメッセージをそのまま返す
-        return Err((StatusCode::BAD_REQUEST, msg));
-    }
+    validate_registration(&payload.username, &payload.password)
+        .map_err(AppError::Validation)?;
 
     // 2. パスワードをハッシュ化 (ブロッキングタスクとして実行)
     // spawn_blocking のクロージャに渡すため、パスワードをクロージャに move する
@@ -245,60 +242,172 @@ async fn register(
         .map_err(|e| {
             // タスクの JoinError (例: パニック)
             tracing::error!("spawn_blocking failed for hash_password: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
+            AppError::Internal("Internal server error".to_string())
         })? // -> Result<String, argon2::Error>
         .map_err(|e| {
             // Argon2 のハッシュ化自体のエラー
             tracing::error!("Failed to hash password: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to hash password".to_string(),
-            )
+            AppError::Internal("Failed to hash password".to_string())
         })?; // -> String
 
     // 3. ユーザーをDBに挿入
-    let result = sqlx::query("INSERT INTO users (username, password_hash) VALUES (?, ?)")
+    // 一意制約違反は `From<sqlx::Error> for AppError` が自動的に
+    // `AppError::UserExists` (409) に変換してくれる
+    let db_result = sqlx::query("INSERT INTO users (username, password_hash) VALUES (?, ?)")
         .bind(&payload.username)
         .bind(&password_hash)
         .execute(&state.db_pool)
-        .await;
+        .await
+        .map_err(AppError::from)?;
+
+    let user = User {
+        user_id: db_result.last_insert_rowid(),
+        username: payload.username.clone(),
+        password_hash: Some(password_hash),
+        role: Role::User,
+    };
+    tracing::info!("New user registered: {}", user.username);
 
-    match result {
-        Ok(db_result) => {
-            let user_id = db_result.last_insert_rowid();
-            let user = User {
-                user_id,
-                username: payload.username.clone(),
-                password_hash,
-            };
-            tracing::info!("New user registered: {}", user.username);
-            Ok(Json(user))
-        }
-        Err(e) => {
-            // sqlx::Error をダウンキャストして、DB固有のエラーか確認
-            if let Some(db_err) = e.as_database_error() {
-                // is_unique_violation() メソッドで一意制約違反かを判定
-                if db_err.is_unique_violation() {
-                    tracing::warn!(
-                        "Failed to register user (username taken): {}",
-                        payload.username
-                    );
-                    return Err((
-                        StatusCode::CONFLICT, // 409 Conflict
-                        "Username already taken".to_string(),
-                    ));
-                }
-            }
+    // ログイン状態で登録を終えられるよう、登録直後にもCookieを発行する
+    let token = create_jwt(user.user_id, user.role, &state.keys, state.config.jwt_expires_in).map_err(|e| {
+        tracing::error!("Failed to generate JWT for user {}: {}", user.user_id, e);
+        AppError::Internal("Failed to generate token".to_string())
+    })?;
+    let refresh_token = refresh_tokens::issue(
+        &state.db_pool,
+        user.user_id,
+        state.config.refresh_token_expires_in_days,
+    )
+    .await?;
+    let session_max_age = state.config.refresh_token_expires_in_days * 24 * 60 * 60;
+    let jar = jar
+        .add(build_auth_cookie(token, state.config.jwt_maxage))
+        .add(build_refresh_cookie(refresh_token, session_max_age))
+        .add(build_csrf_cookie(generate_csrf_token(), session_max_age));
+
+    Ok((jar, Json(user)))
+}
 
-            // その他のDBエラー、またはDB以外のエラー
-            tracing::error!("Failed to register user {}: {}", payload.username, e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to register user".to_string(),
-            ))
-        }
+/// アクセストークンの更新 (POST /auth/refresh)
+///
+/// アクセストークン自体は既に期限切れになっていることを前提とするため、
+/// `auth_middleware` を経由させず、代わりにリフレッシュトークン
+/// (Cookie、またはボディの `refresh_token`) を検証します。
+/// 検証に成功したリフレッシュトークンは使い捨てとしてローテーションされ、
+/// 新しいアクセストークン・リフレッシュトークンの両方を発行し直します。
+#[utoipa::path(
+    post,
+    path = "/api/auth/refresh",
+    tag = "Auth",
+    request_body(
+        content = RefreshPayload,
+        description = "Cookie非対応のクライアント向け。Cookieでリフレッシュトークンを送る場合はボディ自体省略可",
+    ),
+    responses(
+        (
+            status = 200,
+            description = "トークンの再発行に成功",
+            body = AuthToken,
+            example = json!({
+                "token": "ey...（新しいJWTトークン）...",
+                "token_type": "Bearer",
+                "refresh_token": "新しいリフレッシュトークン"
+            })
+        ),
+        (status = 401, description = "リフレッシュトークンが無効または期限切れ"),
+        (status = 500, description = "サーバーエラー")
+    )
+)]
+async fn refresh(
+    State(state): State<AppState>,
+    jar: CookieJar,
+    // Cookieのみで送ってくるブラウザはボディ自体を持たないため、
+    // `Json<RefreshPayload>` ではなく `Option<Json<RefreshPayload>>` で受ける
+    // (必須の `Json` extractor は空ボディだと 400/415 で弾いてしまう)
+    payload: Option<Json<RefreshPayload>>,
+) -> Result<(CookieJar, Json<AuthToken>), AppError> {
+    let presented_token = jar
+        .get(REFRESH_COOKIE_NAME)
+        .map(|c| c.value().to_string())
+        .or(payload.and_then(|Json(payload)| payload.refresh_token))
+        .ok_or_else(|| {
+            tracing::warn!("Refresh failed (no refresh token supplied)");
+            AppError::InvalidRefreshToken
+        })?;
+
+    let (user_id, new_refresh_token) = refresh_tokens::verify_and_rotate(
+        &state.db_pool,
+        &presented_token,
+        state.config.refresh_token_expires_in_days,
+    )
+    .await?;
+
+    let role: Role = sqlx::query_scalar("SELECT role FROM users WHERE user_id = ?")
+        .bind(user_id)
+        .fetch_optional(&state.db_pool)
+        .await?
+        .ok_or_else(|| {
+            tracing::warn!("Refresh failed (user no longer exists): {}", user_id);
+            AppError::InvalidRefreshToken
+        })?;
+
+    let token = create_jwt(user_id, role, &state.keys, state.config.jwt_expires_in).map_err(|e| {
+        tracing::error!("Failed to refresh JWT for user {}: {}", user_id, e);
+        AppError::Internal("Failed to generate token".to_string())
+    })?;
+
+    tracing::info!("Token refreshed for user: {}", user_id);
+
+    let session_max_age = state.config.refresh_token_expires_in_days * 24 * 60 * 60;
+    let jar = jar
+        .add(build_auth_cookie(token.clone(), state.config.jwt_maxage))
+        .add(build_refresh_cookie(new_refresh_token.clone(), session_max_age))
+        .add(build_csrf_cookie(generate_csrf_token(), session_max_age));
+
+    Ok((
+        jar,
+        Json(AuthToken {
+            token,
+            token_type: "Bearer".to_string(),
+            refresh_token: new_refresh_token,
+        }),
+    ))
+}
+
+/// ログアウト (POST /auth/logout)
+///
+/// 認証・リフレッシュ両方のCookieを失効させ、提示されたリフレッシュ
+/// トークンをサーバー側でも失効させます (再利用不可にする)。
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout",
+    tag = "Auth",
+    request_body(
+        content = RefreshPayload,
+        description = "Cookie非対応のクライアント向け。Cookieでリフレッシュトークンを送る場合はボディ自体省略可",
+    ),
+    responses(
+        (status = 204, description = "ログアウト成功 (Cookieを削除)")
+    )
+)]
+async fn logout(
+    State(state): State<AppState>,
+    jar: CookieJar,
+    // refresh と同様、Cookie専用のブラウザはボディを送らないため任意にする
+    payload: Option<Json<RefreshPayload>>,
+) -> Result<(CookieJar, axum::http::StatusCode), AppError> {
+    let presented_token = jar
+        .get(REFRESH_COOKIE_NAME)
+        .map(|c| c.value().to_string())
+        .or(payload.and_then(|Json(payload)| payload.refresh_token));
+
+    if let Some(presented_token) = presented_token {
+        refresh_tokens::revoke(&state.db_pool, &presented_token).await?;
     }
+
+    let jar = jar
+        .add(build_expired_auth_cookie())
+        .add(build_expired_refresh_cookie())
+        .add(build_expired_csrf_cookie());
+    Ok((jar, axum::http::StatusCode::NO_CONTENT))
 }
\ No newline at end of file
@@ -1,13 +1,21 @@
 // src/routes/mod.rs
-use crate::auth::auth_middleware;
+use crate::auth::{auth_middleware, csrf_protection};
 use crate::state::AppState;
 use axum::{Router, middleware};
+use tower_http::compression::{
+    CompressionLayer,
+    predicate::{NotForContentType, SizeAbove},
+};
 use tower_http::services::{ServeDir, ServeFile};
 
 mod admin;
 mod auth;
 mod papers;
 mod swagger;
+mod webauthn;
+
+/// これ未満のサイズのレスポンスは圧縮によるCPUコストが見合わないため圧縮しない
+const MIN_COMPRESSION_SIZE_BYTES: u16 = 256;
 
 /// アプリケーション全体のルーターを構築
 pub fn create_router(app_state: AppState, static_dir: String) -> Router {
@@ -37,29 +45,76 @@ pub fn create_router(app_state: AppState, static_dir: String) -> Router {
         .nest("/api", api_router)
         // ルートパス "/" は静的ファイル配信（SPA対応のため存在しないファイルはindex.htmlを返す）
         .fallback_service(static_files_service)
+        .layer(create_compression_layer())
         .with_state(app_state)
 }
 
+/// レスポンス圧縮レイヤーを構築します
+///
+/// クライアントの `Accept-Encoding` に応じて gzip / deflate のいずれかで
+/// 透過的に圧縮し、`Content-Encoding` / `Vary` ヘッダーを付与します。
+/// 画像など既に圧縮済みのコンテントタイプや、圧縮の恩恵が薄い小さな
+/// レスポンス (`MIN_COMPRESSION_SIZE_BYTES` 未満) は対象外とします。
+fn create_compression_layer() -> CompressionLayer {
+    let compress_when = SizeAbove::new(MIN_COMPRESSION_SIZE_BYTES)
+        .and(NotForContentType::IMAGES)
+        .and(NotForContentType::new("application/gzip"))
+        .and(NotForContentType::new("application/zip"))
+        .and(NotForContentType::new("font"));
+
+    CompressionLayer::new()
+        .gzip(true)
+        .deflate(true)
+        .br(false)
+        .zstd(false)
+        .compress_when(compress_when)
+}
+
 /// 全APIルート（/auth, /papers, /admin）を結合したルーターを構築
 fn create_api_router(app_state: AppState) -> Router<AppState> {
     // 認証が不要なルート (ログイン/登録)
-    let auth_routes = auth::create_auth_routes();
+    // (パスキーの登録/ログインも、本人確認が完了する前の段階なので未認証)
+    let public_auth_routes = auth::create_public_auth_routes()
+        .merge(webauthn::create_webauthn_routes());
+
+    // 認証が不要なルート (Atomフィード)
+    // (フィードリーダーはBearerヘッダーを送れないため、`auth_middleware` ではなく
+    // クエリパラメータのフィードトークンでハンドラ自身が認証を行う)
+    let public_paper_routes = papers::create_public_paper_routes();
+
+    // 認証が必要なルート (自分の情報取得)
+    // (CSRF保護は Cookie の有無に関わらずメソッドで判定するため、
+    // auth_middleware より外側に重ね、不正なリクエストをDBアクセス前に弾く)
+    let protected_auth_routes = auth::create_protected_auth_routes()
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            auth_middleware,
+        ))
+        .layer(middleware::from_fn(csrf_protection));
 
     // 認証が必要なルート (PaperDeck機能)
-    let paper_routes = papers::create_paper_routes().layer(middleware::from_fn_with_state(
-        app_state.clone(),
-        auth_middleware,
-    ));
+    let paper_routes = papers::create_paper_routes()
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            auth_middleware,
+        ))
+        .layer(middleware::from_fn(csrf_protection));
 
     // 認証が必要なルート (管理機能)
-    let admin_routes = admin::create_admin_routes().layer(middleware::from_fn_with_state(
-        app_state.clone(),
-        auth_middleware,
-    ));
+    // (create_admin_routes 自体が "admin:crawl" スコープを要求する require_scope を
+    // 内側のレイヤーとして持つため、ここでは外側に auth_middleware を重ねるだけでよい)
+    let admin_routes = admin::create_admin_routes(app_state.clone())
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            auth_middleware,
+        ))
+        .layer(middleware::from_fn(csrf_protection));
 
     // 全てのAPIルートをマージ
     Router::new()
-        .merge(auth_routes)
+        .merge(public_auth_routes)
+        .merge(public_paper_routes)
+        .merge(protected_auth_routes)
         .merge(paper_routes)
         .merge(admin_routes)
 }
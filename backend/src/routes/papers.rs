@@ -1,24 +1,43 @@
 // src/routes/papers.rs
 use crate::auth::AuthUser;
-use crate::models::{Conference, NextPaperParams, Paper, PaperStatus, StatusPayload};
+use crate::feed_tokens;
+use crate::models::{
+    Conference, ConferenceStats, FeedTokenResponse, NextPaperParams, Paper, PaperStatus,
+    SearchParams, StatsResponse, StatusPayload,
+};
+use crate::recommend::MAX_CANDIDATE_SCAN;
 use crate::state::AppState;
 use axum::{
     Extension, Json, Router,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{StatusCode, header},
+    response::IntoResponse,
     routing::{get, post},
 };
 use chrono::Utc;
+use serde::Deserialize;
 use sqlx::{QueryBuilder, Sqlite};
 use tracing;
 
-/// 論文APIルート (/papers/...) を構築します
+/// 論文APIルート (/papers/...) を構築します (認証必須)
 pub fn create_paper_routes() -> Router<AppState> {
     Router::new()
         .route("/papers/conferences", get(get_conferences))
         .route("/papers/liked", get(get_liked_papers))
+        .route("/papers/liked/export", get(export_liked_papers))
+        .route("/papers/liked/feed_token", get(get_feed_token))
         .route("/papers/next", get(get_next_paper))
-        .route("/papers/:paper_id/status", post(set_paper_status))
+        .route("/papers/search", get(search_papers))
+        .route("/papers/stats", get(get_stats))
+        .route(
+            "/papers/:paper_id/status",
+            post(set_paper_status).delete(delete_paper_status),
+        )
+}
+
+/// 論文APIルートのうち、認証不要なもの (フィードトークンで自前認証する) を構築します
+pub fn create_public_paper_routes() -> Router<AppState> {
+    Router::new().route("/papers/liked/feed.atom", get(get_liked_feed))
 }
 
 /// 登録されている学会名と年度のリストを取得 (GET /papers/conferences)
@@ -68,6 +87,126 @@ async fn get_conferences(
     }
 }
 
+/// 学会・年度ごとの内訳1行分 (GET /papers/stats の集計結果)
+#[derive(Debug, sqlx::FromRow)]
+struct ConferenceStatsRow {
+    conference_name: String,
+    year: i64,
+    available: i64,
+    rated: i64,
+    liked: i64,
+}
+
+/// 読書進捗の集計を取得 (GET /papers/stats)
+///
+/// `get_next_paper` と同じ `conference`/`year` フィルタを受け付け、
+/// ダッシュボードを特定の学会に絞り込めるようにします。
+#[utoipa::path(
+    get,
+    path = "/api/papers/stats",
+    tag = "Papers",
+    params(
+        ("conference" = Option<String>, Query, description = "学会名", example = "USENIX Security"),
+        ("year" = Option<i64>, Query, description = "年度", example = 2025)
+    ),
+    responses(
+        (status = 200, description = "閲覧状況の集計", body = StatsResponse),
+        (status = 500, description = "サーバーエラー")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_stats(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(params): Query<NextPaperParams>,
+) -> Result<Json<StatsResponse>, (StatusCode, String)> {
+    let current_user_id = auth_user.user_id;
+
+    let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+        r#"
+        SELECT
+            p.conference_name,
+            p.year,
+            COUNT(*) AS available,
+            COUNT(ups.paper_id) AS rated,
+            SUM(CASE WHEN ups.liked_at IS NOT NULL THEN 1 ELSE 0 END) AS liked
+        FROM papers p
+        LEFT JOIN user_paper_status ups
+            ON p.id = ups.paper_id AND ups.user_id =
+        "#,
+    );
+    query_builder.push_bind(current_user_id);
+    query_builder.push(" WHERE 1=1 ");
+
+    if let Some(conf_name) = &params.conference {
+        if !conf_name.is_empty() {
+            query_builder.push(" AND p.conference_name = ");
+            query_builder.push_bind(conf_name);
+        }
+    }
+    if let Some(year) = params.year {
+        query_builder.push(" AND p.year = ");
+        query_builder.push_bind(year);
+    }
+
+    query_builder.push(" GROUP BY p.conference_name, p.year ORDER BY p.year DESC, p.conference_name ASC");
+
+    let rows = query_builder
+        .build_query_as::<ConferenceStatsRow>()
+        .fetch_all(&state.db_pool)
+        .await;
+
+    match rows {
+        Ok(rows) => {
+            let mut total_available = 0;
+            let mut total_rated = 0;
+            let mut total_liked = 0;
+
+            let by_conference = rows
+                .into_iter()
+                .map(|row| {
+                    total_available += row.available;
+                    total_rated += row.rated;
+                    total_liked += row.liked;
+
+                    let coverage_percent = if row.available > 0 {
+                        (row.rated as f64 / row.available as f64 * 1000.0).round() / 10.0
+                    } else {
+                        0.0
+                    };
+
+                    ConferenceStats {
+                        conference_name: row.conference_name,
+                        year: row.year,
+                        available: row.available,
+                        rated: row.rated,
+                        liked: row.liked,
+                        coverage_percent,
+                    }
+                })
+                .collect();
+
+            Ok(Json(StatsResponse {
+                total_available,
+                total_rated,
+                total_liked,
+                by_conference,
+            }))
+        }
+        Err(e) => {
+            tracing::error!(
+                "Database error in get_stats for user {}: {}",
+                current_user_id,
+                e
+            );
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", e),
+            ))
+        }
+    }
+}
+
 /// いいねした論文のリストを取得 (GET /papers/liked)
 #[utoipa::path(
     get,
@@ -127,14 +266,400 @@ async fn get_liked_papers(
     }
 }
 
-/// 次に評価すべき論文をランダムに1件取得 (GET /papers/next)
+/// BibTeXの特殊文字 (`\`, `{`, `}`) を最低限エスケープする
+fn escape_bibtex(input: &str) -> String {
+    input
+        .replace('\\', "\\textbackslash{}")
+        .replace('{', "\\{")
+        .replace('}', "\\}")
+}
+
+/// 著者リストの先頭著者の姓を取り出す (例: "Jane Doe, John Smith" -> "Doe")
+fn first_author_surname(authors: &str) -> String {
+    authors
+        .split(',')
+        .next()
+        .unwrap_or("Unknown")
+        .trim()
+        .split_whitespace()
+        .last()
+        .unwrap_or("Unknown")
+        .to_string()
+}
+
+/// 英数字以外を取り除いた、引用キー用の短いスラッグを生成する
+fn slugify(input: &str) -> String {
+    input
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .take(24)
+        .collect()
+}
+
+/// 1論文分のBibTeXエントリを生成する (姓+年+タイトルスラッグの引用キー)
+fn paper_to_bibtex(paper: &Paper) -> String {
+    let surname = paper
+        .authors
+        .as_deref()
+        .map(first_author_surname)
+        .unwrap_or_else(|| "unknown".to_string());
+    let cite_key = format!("{}{}{}", slugify(&surname), paper.year, slugify(&paper.title));
+
+    let mut entry = format!(
+        "@inproceedings{{{cite_key},\n  author = {{{authors}}},\n  title = {{{title}}},\n  booktitle = {{{booktitle}}},\n  year = {{{year}}},\n",
+        cite_key = cite_key,
+        authors = escape_bibtex(paper.authors.as_deref().unwrap_or("Unknown")),
+        title = escape_bibtex(&paper.title),
+        booktitle = escape_bibtex(&paper.conference_name),
+        year = paper.year,
+    );
+    if let Some(url) = &paper.url {
+        entry.push_str(&format!("  url = {{{}}},\n", escape_bibtex(url)));
+    }
+    entry.push_str("}\n");
+    entry
+}
+
+/// GET /papers/liked/export のクエリパラメータ
+#[derive(Debug, Deserialize)]
+struct ExportParams {
+    /// "bibtex" を指定するとBibTeX形式、それ以外 (未指定含む) はJSON配列を返す
+    format: Option<String>,
+}
+
+/// いいねした論文を文献管理ツール向けにエクスポート (GET /papers/liked/export)
+#[utoipa::path(
+    get,
+    path = "/api/papers/liked/export",
+    tag = "Papers",
+    params(
+        ("format" = Option<String>, Query, description = "\"bibtex\" または \"json\" (省略時はjson)", example = "bibtex")
+    ),
+    responses(
+        (status = 200, description = "いいねした論文一覧 (BibTeXまたはJSON)"),
+        (status = 500, description = "サーバーエラー")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn export_liked_papers(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(params): Query<ExportParams>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let current_user_id = auth_user.user_id;
+
+    let papers = sqlx::query_as::<_, Paper>(
+        r#"
+        SELECT p.*
+        FROM papers p
+        JOIN user_paper_status ups ON p.id = ups.paper_id
+        WHERE ups.user_id = ? AND ups.liked_at IS NOT NULL
+        ORDER BY ups.liked_at DESC
+        "#,
+    )
+    .bind(current_user_id)
+    .fetch_all(&state.db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            "Database error in export_liked_papers for user {}: {}",
+            current_user_id,
+            e
+        );
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Database error: {}", e),
+        )
+    })?;
+
+    match params.format.as_deref() {
+        Some("bibtex") => {
+            let body = papers.iter().map(paper_to_bibtex).collect::<Vec<_>>().join("\n");
+            Ok((
+                [(header::CONTENT_TYPE, "application/x-bibtex; charset=utf-8")],
+                body,
+            )
+                .into_response())
+        }
+        _ => Ok(Json(papers).into_response()),
+    }
+}
+
+/// いいねしたリストのAtomフィードURLに埋め込む、自分専用のフィードトークンを取得
+/// (まだ発行されていなければこの呼び出しで新規発行される)
+#[utoipa::path(
+    get,
+    path = "/api/papers/liked/feed_token",
+    tag = "Papers",
+    responses(
+        (status = 200, description = "フィードトークンを取得", body = FeedTokenResponse),
+        (status = 500, description = "サーバーエラー")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn get_feed_token(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<Json<FeedTokenResponse>, (StatusCode, String)> {
+    let current_user_id = auth_user.user_id;
+
+    match feed_tokens::get_or_create(&state.db_pool, current_user_id).await {
+        Ok(token) => Ok(Json(FeedTokenResponse { token })),
+        Err(e) => {
+            tracing::error!(
+                "Database error in get_feed_token for user {}: {}",
+                current_user_id,
+                e
+            );
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", e),
+            ))
+        }
+    }
+}
+
+/// Atomフィード用、いいねした論文の行 (liked_at も含む)
+#[derive(Debug, sqlx::FromRow)]
+struct LikedFeedEntry {
+    id: i64,
+    title: String,
+    url: Option<String>,
+    authors: Option<String>,
+    abstract_text: Option<String>,
+    liked_at: String,
+}
+
+/// GET /papers/liked/feed.atom のクエリパラメータ
+#[derive(Debug, Deserialize)]
+struct FeedParams {
+    /// `GET /papers/liked/feed_token` で発行した個人用フィードトークン
+    token: String,
+}
+
+/// XMLの特殊文字をエスケープする (タイトル・著者名・要約に含まれうるため)
+fn escape_xml(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// いいねした論文のAtomフィード (GET /papers/liked/feed.atom)
+///
+/// フィードリーダーはBearerヘッダーを送れないため、通常の `AuthUser` ではなく
+/// `token` クエリパラメータの個人用フィードトークンで認証する
+/// (そのため `auth_middleware` を経由しない公開ルートとして登録される)。
+#[utoipa::path(
+    get,
+    path = "/api/papers/liked/feed.atom",
+    tag = "Papers",
+    params(
+        ("token" = String, Query, description = "GET /papers/liked/feed_token で発行した個人用フィードトークン")
+    ),
+    responses(
+        (status = 200, description = "いいねした論文のAtom 1.0フィード", body = String, content_type = "application/atom+xml"),
+        (status = 401, description = "フィードトークンが無効"),
+        (status = 500, description = "サーバーエラー")
+    )
+)]
+async fn get_liked_feed(
+    State(state): State<AppState>,
+    Query(params): Query<FeedParams>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user_id = feed_tokens::user_id_for_token(&state.db_pool, &params.token)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error in get_liked_feed (token lookup): {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", e),
+            )
+        })?
+        .ok_or((StatusCode::UNAUTHORIZED, "Invalid feed token".to_string()))?;
+
+    let entries: Vec<LikedFeedEntry> = sqlx::query_as(
+        r#"
+        SELECT p.id, p.title, p.url, p.authors, p.abstract_text, ups.liked_at
+        FROM papers p
+        JOIN user_paper_status ups ON p.id = ups.paper_id
+        WHERE ups.user_id = ? AND ups.liked_at IS NOT NULL
+        ORDER BY ups.liked_at DESC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(&state.db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            "Database error in get_liked_feed for user {}: {}",
+            user_id,
+            e
+        );
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Database error: {}", e),
+        )
+    })?;
+
+    let feed_updated = entries
+        .first()
+        .map(|e| e.liked_at.clone())
+        .unwrap_or_else(|| Utc::now().to_rfc3339());
+
+    let mut entries_xml = String::new();
+    for entry in &entries {
+        let link = entry.url.clone().unwrap_or_default();
+        let entry_id = if link.is_empty() {
+            format!("urn:paperdeck:paper:{}", entry.id)
+        } else {
+            link.clone()
+        };
+        entries_xml.push_str(&format!(
+            r#"  <entry>
+    <title>{title}</title>
+    <id>{id}</id>
+    <link href="{link}"/>
+    <author><name>{author}</name></author>
+    <summary>{summary}</summary>
+    <updated>{updated}</updated>
+  </entry>
+"#,
+            title = escape_xml(&entry.title),
+            id = escape_xml(&entry_id),
+            link = escape_xml(&link),
+            author = escape_xml(entry.authors.as_deref().unwrap_or("Unknown")),
+            summary = escape_xml(entry.abstract_text.as_deref().unwrap_or("")),
+            updated = escape_xml(&entry.liked_at),
+        ));
+    }
+
+    let body = format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>PaperDeck - Liked Papers</title>
+  <id>urn:paperdeck:liked-feed:{user_id}</id>
+  <updated>{updated}</updated>
+{entries}</feed>
+"#,
+        user_id = user_id,
+        updated = escape_xml(&feed_updated),
+        entries = entries_xml,
+    );
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")],
+        body,
+    ))
+}
+
+/// ユーザー入力の検索語を FTS5 の `MATCH` 構文として安全な形に変換する
+///
+/// FTS5 の `MATCH` 引数は独自のクエリ構文 (`"`, `-`, `:`, `(`, `*` など) を
+/// 持つため、ユーザー入力をそのまま `push_bind` すると、閉じていない `"` や
+/// 末尾の `-`、`c++` のような語で `fts5: syntax error` が発生し、
+/// 500 としてそのまま漏れてしまう。各トークンをダブルクォートで囲んだ
+/// フレーズ (内部の `"` は `""` にエスケープ) として扱うことで、常に
+/// 構文上妥当な `MATCH` 引数に変換する。
+/// 空白のみの入力など、トークンが1つも残らない場合は `None` を返す。
+fn sanitize_fts_query(raw: &str) -> Option<String> {
+    let quoted: Vec<String> = raw
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+
+    if quoted.is_empty() {
+        None
+    } else {
+        Some(quoted.join(" "))
+    }
+}
+
+/// `mode=relevant` 用に、いいねした論文群に近い未評価論文のIDを1件探す
+///
+/// プロファイル (いいねの平均ベクトル) を構築できない場合や、候補が1件も
+/// 見つからない場合は `Ok(None)` を返し、呼び出し元は通常の選択 (`q` 指定時は
+/// bm25 順、未指定時はランダム) にフォールバックします。
+///
+/// `fts_query` (`sanitize_fts_query` 済みの `q`) が指定されている場合は、
+/// 候補サンプリングの時点で `papers_fts MATCH` による絞り込みも適用する。
+/// これをしないと `mode=relevant&q=...` を同時指定したときに `q` が無視され、
+/// 全文検索条件を満たさない論文までTF-IDFスコアリングの対象になってしまう。
+async fn find_relevant_paper_id(
+    state: &AppState,
+    current_user_id: i64,
+    params: &NextPaperParams,
+    fts_query: Option<&str>,
+) -> Result<Option<i64>, sqlx::Error> {
+    let liked_paper_ids: Vec<i64> = sqlx::query_scalar(
+        "SELECT paper_id FROM user_paper_status WHERE user_id = ? AND liked_at IS NOT NULL",
+    )
+    .bind(current_user_id)
+    .fetch_all(&state.db_pool)
+    .await?;
+
+    if liked_paper_ids.is_empty() {
+        return Ok(None);
+    }
+
+    // 既存のランダム選択と同じフィルタを適用した、未評価の候補論文IDをサンプリングする
+    let mut candidate_query: QueryBuilder<Sqlite> = QueryBuilder::new(
+        r#"
+        SELECT p.id
+        FROM papers p
+        LEFT JOIN user_paper_status ups
+            ON p.id = ups.paper_id AND ups.user_id =
+        "#,
+    );
+    candidate_query.push_bind(current_user_id);
+
+    if fts_query.is_some() {
+        candidate_query.push(" JOIN papers_fts ON papers_fts.rowid = p.id ");
+    }
+
+    candidate_query.push(" WHERE ups.created_at IS NULL ");
+
+    if let Some(conf_name) = &params.conference {
+        if !conf_name.is_empty() {
+            candidate_query.push(" AND p.conference_name = ");
+            candidate_query.push_bind(conf_name);
+        }
+    }
+    if let Some(year) = params.year {
+        candidate_query.push(" AND p.year = ");
+        candidate_query.push_bind(year);
+    }
+    if let Some(fts_query) = fts_query {
+        candidate_query.push(" AND papers_fts MATCH ");
+        candidate_query.push_bind(fts_query.to_string());
+    }
+    // rowid (投入順) のまま LIMIT すると、常に最も古い論文だけがスキャンされ
+    // TF-IDFスコアリングの対象から外れた論文が出てきてしまうため、
+    // ORDER BY RANDOM() で毎回異なる代表部分集合を取る
+    candidate_query.push(" ORDER BY RANDOM() LIMIT ");
+    candidate_query.push_bind(MAX_CANDIDATE_SCAN);
+
+    let candidate_ids: Vec<i64> = candidate_query
+        .build_query_scalar()
+        .fetch_all(&state.db_pool)
+        .await?;
+
+    Ok(state.recommender.most_relevant(&liked_paper_ids, &candidate_ids))
+}
+
+/// 次に評価すべき論文を1件取得 (GET /papers/next)
 #[utoipa::path(
     get,
     path = "/api/papers/next",
     tag = "Papers",
     params(
         ("conference" = Option<String>, Query, description = "学会名", example = "USENIX Security"),
-        ("year" = Option<i64>, Query, description = "年度", example = 2025)
+        ("year" = Option<i64>, Query, description = "年度", example = 2025),
+        ("mode" = Option<String>, Query, description = "\"relevant\" を指定すると、いいねした論文と似た論文を優先して返す (qも指定した場合はその検索条件を満たす候補に限定される)", example = "relevant"),
+        ("q" = Option<String>, Query, description = "指定すると全文検索条件で絞り込む。mode未指定時は関連度 (bm25) が最も高い1件を返す", example = "differential privacy")
     ),
     responses(
         (
@@ -168,17 +693,74 @@ async fn get_next_paper(
 ) -> Result<Json<Paper>, (StatusCode, String)> {
     let current_user_id = auth_user.user_id;
 
+    // FTS5構文として安全なフレーズに変換してから使う (malformed な入力で
+    // 500 にならないよう、閉じていない `"` 等はここで無害化される)。
+    // mode=relevant と q を同時に指定された場合にも使うため、フォールバックの
+    // ランダム選択より前に一度だけ計算しておく。
+    let fts_query = params.q.as_deref().and_then(sanitize_fts_query);
+
+    // mode=relevant の場合は、いいねした論文に近い論文を優先して探す。
+    // `q` も指定されていれば、その全文検索条件を満たす候補の中から選ぶ。
+    // プロファイルが作れない (いいねが0件) か候補が見つからない場合は、
+    // 以下の通常の選択 (`q` 指定時は bm25 順、未指定時はランダム) にフォールバックする。
+    if params.mode.as_deref() == Some("relevant") {
+        match find_relevant_paper_id(&state, current_user_id, &params, fts_query.as_deref()).await {
+            Ok(Some(paper_id)) => {
+                let paper =
+                    sqlx::query_as::<_, Paper>("SELECT * FROM papers WHERE id = ?")
+                        .bind(paper_id)
+                        .fetch_optional(&state.db_pool)
+                        .await;
+                match paper {
+                    Ok(Some(paper)) => return Ok(Json(paper)),
+                    Ok(None) => {} // レース等で消えていた場合は通常の選択にフォールバック
+                    Err(e) => {
+                        tracing::error!(
+                            "Database error fetching relevant paper {} for user {}: {}",
+                            paper_id,
+                            current_user_id,
+                            e
+                        );
+                        return Err((
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            format!("Database error: {}", e),
+                        ));
+                    }
+                }
+            }
+            Ok(None) => {} // プロファイルなし、または候補なし: 通常の選択にフォールバック
+            Err(e) => {
+                tracing::error!(
+                    "Database error in find_relevant_paper_id for user {}: {}",
+                    current_user_id,
+                    e
+                );
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Database error: {}", e),
+                ));
+            }
+        }
+    }
+
+    let has_query = fts_query.is_some();
+
     // QueryBuilder を使って動的にクエリを構築
     let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(
         r#"
         SELECT p.*
         FROM papers p
-        LEFT JOIN user_paper_status ups 
-            ON p.id = ups.paper_id AND ups.user_id = 
+        LEFT JOIN user_paper_status ups
+            ON p.id = ups.paper_id AND ups.user_id =
         "#,
     );
     query_builder.push_bind(current_user_id);
 
+    // q パラメータがある場合のみ、FTS5の全文検索テーブルを結合する
+    if has_query {
+        query_builder.push(" JOIN papers_fts ON papers_fts.rowid = p.id ");
+    }
+
     // 残りの WHERE 句を追加
     query_builder.push(" WHERE ups.created_at IS NULL ");
 
@@ -196,8 +778,18 @@ async fn get_next_paper(
         query_builder.push_bind(year);
     }
 
-    // 最後にランダムソートとリミットを追加
-    query_builder.push(" ORDER BY RANDOM() LIMIT 1");
+    // q パラメータが存在する場合は全文検索条件を追加
+    if let Some(fts_query) = &fts_query {
+        query_builder.push(" AND papers_fts MATCH ");
+        query_builder.push_bind(fts_query.clone());
+    }
+
+    // q 指定時は関連度 (bm25) 順、未指定時は従来通りランダムに1件選ぶ
+    if has_query {
+        query_builder.push(" ORDER BY bm25(papers_fts) LIMIT 1");
+    } else {
+        query_builder.push(" ORDER BY RANDOM() LIMIT 1");
+    }
 
     let result = query_builder
         .build_query_as::<Paper>()
@@ -224,19 +816,26 @@ async fn get_next_paper(
         // --- 見つからないケース (404) ---
         Ok(None) => {
             // フィルタに一致する論文がそもそも存在するかを確認する
-            let mut check_query: QueryBuilder<Sqlite> =
-                QueryBuilder::new("SELECT 1 FROM papers WHERE 1=1 ");
+            let mut check_query: QueryBuilder<Sqlite> = QueryBuilder::new(if has_query {
+                "SELECT 1 FROM papers p JOIN papers_fts ON papers_fts.rowid = p.id WHERE 1=1 "
+            } else {
+                "SELECT 1 FROM papers p WHERE 1=1 "
+            });
 
             if let Some(conf_name) = &params.conference {
                 if !conf_name.is_empty() {
-                    check_query.push(" AND conference_name = ");
+                    check_query.push(" AND p.conference_name = ");
                     check_query.push_bind(conf_name);
                 }
             }
             if let Some(year) = params.year {
-                check_query.push(" AND year = ");
+                check_query.push(" AND p.year = ");
                 check_query.push_bind(year);
             }
+            if let Some(fts_query) = &fts_query {
+                check_query.push(" AND papers_fts MATCH ");
+                check_query.push_bind(fts_query.clone());
+            }
             check_query.push(" LIMIT 1");
 
             let check_result = check_query.build().fetch_optional(&state.db_pool).await;
@@ -275,6 +874,83 @@ async fn get_next_paper(
     }
 }
 
+/// GET /papers/search で返す最大件数 (レイテンシを抑えるための上限)
+const MAX_SEARCH_RESULTS: i64 = 50;
+
+/// 全文検索で論文を探す (GET /papers/search)
+///
+/// `title` / `authors` / `abstract_text` を対象としたFTS5全文検索を行い、
+/// 関連度 (bm25) の高い順に最大 `MAX_SEARCH_RESULTS` 件を返します。
+/// 評価済みかどうかに関わらず、コーパス全体から検索します。
+#[utoipa::path(
+    get,
+    path = "/api/papers/search",
+    tag = "Papers",
+    params(
+        ("q" = String, Query, description = "全文検索語", example = "differential privacy"),
+        ("conference" = Option<String>, Query, description = "学会名", example = "USENIX Security"),
+        ("year" = Option<i64>, Query, description = "年度", example = 2025)
+    ),
+    responses(
+        (status = 200, description = "関連度順の論文リスト", body = Vec<Paper>),
+        (status = 400, description = "検索語が空 (空白のみ含む)"),
+        (status = 500, description = "サーバーエラー")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn search_papers(
+    State(state): State<AppState>,
+    Extension(_auth_user): Extension<AuthUser>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<Vec<Paper>>, (StatusCode, String)> {
+    // FTS5構文として安全なフレーズに変換してから使う (malformed な入力で
+    // 500 にならないよう、閉じていない `"` 等はここで無害化される)
+    let fts_query = sanitize_fts_query(&params.q).ok_or((
+        StatusCode::BAD_REQUEST,
+        "Search query must not be empty".to_string(),
+    ))?;
+
+    let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+        r#"
+        SELECT p.*
+        FROM papers p
+        JOIN papers_fts ON papers_fts.rowid = p.id
+        WHERE papers_fts MATCH
+        "#,
+    );
+    query_builder.push_bind(fts_query);
+
+    if let Some(conf_name) = &params.conference {
+        if !conf_name.is_empty() {
+            query_builder.push(" AND p.conference_name = ");
+            query_builder.push_bind(conf_name);
+        }
+    }
+    if let Some(year) = params.year {
+        query_builder.push(" AND p.year = ");
+        query_builder.push_bind(year);
+    }
+
+    query_builder.push(" ORDER BY bm25(papers_fts) LIMIT ");
+    query_builder.push_bind(MAX_SEARCH_RESULTS);
+
+    let result = query_builder
+        .build_query_as::<Paper>()
+        .fetch_all(&state.db_pool)
+        .await;
+
+    match result {
+        Ok(papers) => Ok(Json(papers)),
+        Err(e) => {
+            tracing::error!("Database error in search_papers: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", e),
+            ))
+        }
+    }
+}
+
 /// 論文の評価ステータスを設定 (POST /papers/:paper_id/status)
 #[utoipa::path(
     post,
@@ -360,3 +1036,84 @@ async fn set_paper_status(
         }
     }
 }
+
+/// DELETE /papers/:paper_id/status のクエリパラメータ
+#[derive(Debug, Deserialize)]
+struct DeleteStatusParams {
+    /// "unlike" を指定すると、いいねだけを取り消して既読記録は残す。
+    /// 未指定の場合はステータス行自体を削除し、未評価の状態に戻す。
+    action: Option<String>,
+}
+
+/// 論文の評価ステータスを取り消す (DELETE /papers/:paper_id/status)
+///
+/// ミススワイプを取り消せるよう、スワイプ直後に「取り消す」操作を提供するための
+/// エンドポイント。`action=unlike` のときはいいねだけを取り消し (既読記録は残す)、
+/// それ以外は行自体を削除して `get_next_paper` の未評価プールに戻す。
+#[utoipa::path(
+    delete,
+    path = "/api/papers/{paper_id}/status",
+    tag = "Papers",
+    params(
+        ("paper_id" = i64, Path, description = "論文ID", example = 123),
+        ("action" = Option<String>, Query, description = "\"unlike\" を指定するといいねのみ取り消す", example = "unlike")
+    ),
+    responses(
+        (status = 204, description = "ステータスの取り消しに成功"),
+        (status = 404, description = "取り消すステータスが存在しない"),
+        (status = 500, description = "サーバーエラー")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn delete_paper_status(
+    State(state): State<AppState>,
+    Path(paper_id): Path<i64>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(params): Query<DeleteStatusParams>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let current_user_id = auth_user.user_id;
+    let is_unlike = params.action.as_deref() == Some("unlike");
+
+    let result = if is_unlike {
+        sqlx::query(
+            "UPDATE user_paper_status SET liked_at = NULL WHERE user_id = ? AND paper_id = ?",
+        )
+        .bind(current_user_id)
+        .bind(paper_id)
+        .execute(&state.db_pool)
+        .await
+    } else {
+        sqlx::query("DELETE FROM user_paper_status WHERE user_id = ? AND paper_id = ?")
+            .bind(current_user_id)
+            .bind(paper_id)
+            .execute(&state.db_pool)
+            .await
+    };
+
+    match result {
+        Ok(res) if res.rows_affected() > 0 => {
+            tracing::info!(
+                "User {} {} status for paper {}",
+                current_user_id,
+                if is_unlike { "unliked" } else { "cleared" },
+                paper_id
+            );
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Ok(_) => Err((
+            StatusCode::NOT_FOUND,
+            "No status found for this paper".to_string(),
+        )),
+        Err(e) => {
+            tracing::error!(
+                "Database error in delete_paper_status for user {}: {}",
+                current_user_id,
+                e
+            );
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", e),
+            ))
+        }
+    }
+}
@@ -7,8 +7,10 @@ use utoipa_swagger_ui::SwaggerUi;
 
 // models.rs から ToSchema を実装した型をすべてインポートする
 use crate::models::{
-    AuthToken, Conference, CrawlPayload, CrawlResponse, LoginPayload, Paper, PaperStatus,
-    RegisterPayload, StatusPayload, User,
+    AuthToken, Conference, ConferenceStats, CrawlJob, CrawlJobStatus, CrawlPayload, CrawlResponse,
+    FeedTokenResponse, LoginPayload, Paper, PaperStatus, RefreshPayload, RegisterPayload, Role,
+    StatsResponse, StatusPayload, User, WebauthnChallenge, WebauthnLoginFinishPayload,
+    WebauthnLoginStartPayload, WebauthnRegisterFinishPayload, WebauthnRegisterStartPayload,
 };
 
 // --- APIドキュメントの定義 ---
@@ -17,18 +19,35 @@ use crate::models::{
 #[openapi(
     paths(
         crate::routes::admin::trigger_crawl,
+        crate::routes::admin::list_crawl_jobs,
+        crate::routes::admin::get_crawl_job,
         crate::routes::auth::register,
         crate::routes::auth::login,
+        crate::routes::auth::refresh,
+        crate::routes::auth::logout,
+        crate::routes::webauthn::register_start,
+        crate::routes::webauthn::register_finish,
+        crate::routes::webauthn::login_start,
+        crate::routes::webauthn::login_finish,
         crate::routes::papers::get_conferences,
         crate::routes::papers::get_liked_papers,
+        crate::routes::papers::export_liked_papers,
+        crate::routes::papers::get_feed_token,
+        crate::routes::papers::get_liked_feed,
         crate::routes::papers::get_next_paper,
+        crate::routes::papers::search_papers,
+        crate::routes::papers::get_stats,
         crate::routes::papers::set_paper_status,
+        crate::routes::papers::delete_paper_status,
     ),
     components(
         schemas(
             // src/models.rs で ToSchema を derive した型
-            Paper, User, RegisterPayload, LoginPayload, AuthToken,
-            StatusPayload, CrawlPayload, PaperStatus, CrawlResponse, Conference
+            Paper, User, RegisterPayload, LoginPayload, AuthToken, RefreshPayload,
+            StatusPayload, CrawlPayload, PaperStatus, CrawlResponse, Conference, Role,
+            CrawlJob, CrawlJobStatus, FeedTokenResponse, ConferenceStats, StatsResponse,
+            WebauthnChallenge, WebauthnRegisterStartPayload, WebauthnRegisterFinishPayload,
+            WebauthnLoginStartPayload, WebauthnLoginFinishPayload
         )
     ),
     tags(
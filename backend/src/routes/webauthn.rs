@@ -0,0 +1,335 @@
+// src/routes/webauthn.rs
+use axum::{Json, Router, extract::State, routing::post};
+use axum_extra::extract::cookie::CookieJar;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use tracing;
+use webauthn_rs::prelude::{Passkey, PublicKeyCredential, RegisterPublicKeyCredential};
+
+use crate::auth::{
+    build_auth_cookie, build_csrf_cookie, build_refresh_cookie, create_jwt, generate_csrf_token,
+    validate_username,
+};
+use crate::error::AppError;
+use crate::models::{
+    AuthToken, User, WebauthnChallenge, WebauthnLoginFinishPayload, WebauthnLoginStartPayload,
+    WebauthnRegisterFinishPayload, WebauthnRegisterStartPayload,
+};
+use crate::refresh_tokens;
+use crate::state::AppState;
+
+/// パスキー (WebAuthn) 認証ルート (/auth/webauthn/...) を構築します
+/// (パスワード認証より前に名乗りを上げる必要があるため、全て未認証で公開します)
+pub fn create_webauthn_routes() -> Router<AppState> {
+    Router::new()
+        .route("/auth/webauthn/register/start", post(register_start))
+        .route("/auth/webauthn/register/finish", post(register_finish))
+        .route("/auth/webauthn/login/start", post(login_start))
+        .route("/auth/webauthn/login/finish", post(login_finish))
+}
+
+/// そのユーザーに紐づく登録済みパスキーを取得する
+async fn fetch_passkeys(state: &AppState, user_id: i64) -> Result<Vec<Passkey>, AppError> {
+    let rows: Vec<(String,)> =
+        sqlx::query_as("SELECT passkey_data FROM webauthn_credentials WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_all(&state.db_pool)
+            .await?;
+
+    rows.into_iter()
+        .map(|(data,)| {
+            serde_json::from_str::<Passkey>(&data).map_err(|e| {
+                tracing::error!("Failed to deserialize stored passkey: {}", e);
+                AppError::Internal("Corrupted passkey data".to_string())
+            })
+        })
+        .collect()
+}
+
+/// パスキー登録開始 (POST /auth/webauthn/register/start)
+///
+/// このエンドポイントは未認証で公開されているため、新規アカウント作成の
+/// みを扱う。既存のユーザー名を指定して開始しても、そのユーザーには一切
+/// 紐付けない (でなければ、他人のユーザー名を指定して自分の認証器を
+/// 登録し、以後そのユーザーとしてログインできてしまうアカウント乗っ取りが
+/// 可能になる)。既存アカウントへのパスキー追加は別途 `auth_middleware`
+/// 配下の認証済みエンドポイントとして実装すべき機能であり、ここでは扱わない。
+/// ユーザー行自体も、このセレモニーが完了する (`register_finish`) までは
+/// 作成しない。先に作成してしまうと、セレモニーを完了せずに呼ぶだけで
+/// ユーザー名を専有 (squatting) できてしまう。
+#[utoipa::path(
+    post,
+    path = "/api/auth/webauthn/register/start",
+    tag = "Auth",
+    request_body(
+        content = WebauthnRegisterStartPayload,
+        example = json!({"username": "testuser"})
+    ),
+    responses(
+        (status = 200, description = "登録セレモニーを開始", body = WebauthnChallenge),
+        (status = 400, description = "ユーザー名が不正"),
+        (status = 500, description = "サーバーエラー")
+    )
+)]
+async fn register_start(
+    State(state): State<AppState>,
+    Json(payload): Json<WebauthnRegisterStartPayload>,
+) -> Result<Json<WebauthnChallenge>, AppError> {
+    validate_username(&payload.username)
+        .map_err(AppError::Validation)?;
+
+    let (challenge_id, ccr) = state.webauthn.start_registration(&payload.username).await?;
+
+    tracing::info!(
+        "WebAuthn registration started for new account '{}'",
+        payload.username
+    );
+
+    Ok(Json(WebauthnChallenge {
+        challenge_id,
+        options: serde_json::to_value(ccr)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize challenge: {}", e)))?,
+    }))
+}
+
+/// パスキー登録完了 (POST /auth/webauthn/register/finish)
+///
+/// `register_start` ではユーザー行を作らないため、ここでセレモニー検証が
+/// 成功して初めて新規ユーザーをDBに作成する。ユーザー名の一意制約違反は
+/// (他のリクエストが先に同じユーザー名で登録を終えていた場合など)
+/// `register()` と同様 `AppError::from` が自動的に `UserExists` (409) に
+/// 変換する。
+#[utoipa::path(
+    post,
+    path = "/api/auth/webauthn/register/finish",
+    tag = "Auth",
+    request_body(content = WebauthnRegisterFinishPayload),
+    responses(
+        (status = 200, description = "登録成功、ログイン済みCookieとトークンを発行", body = AuthToken),
+        (status = 400, description = "セレモニーの検証に失敗"),
+        (status = 409, description = "ユーザー名が既に使用されている"),
+        (status = 500, description = "サーバーエラー")
+    )
+)]
+async fn register_finish(
+    State(state): State<AppState>,
+    jar: CookieJar,
+    Json(payload): Json<WebauthnRegisterFinishPayload>,
+) -> Result<(CookieJar, Json<AuthToken>), AppError> {
+    let credential: RegisterPublicKeyCredential = serde_json::from_value(payload.credential)
+        .map_err(|e| AppError::Validation(format!("Invalid credential payload: {}", e)))?;
+
+    let (username, passkey) = state
+        .webauthn
+        .finish_registration(&payload.challenge_id, &credential)
+        .await?;
+
+    // パスキーのみのアカウントとして新規作成 (password_hash は NULL)
+    let db_result = sqlx::query("INSERT INTO users (username, password_hash) VALUES (?, NULL)")
+        .bind(&username)
+        .execute(&state.db_pool)
+        .await
+        .map_err(AppError::from)?;
+    let user_id = db_result.last_insert_rowid();
+
+    let credential_id = URL_SAFE_NO_PAD.encode(passkey.cred_id().as_ref());
+    let passkey_data = serde_json::to_string(&passkey)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize passkey: {}", e)))?;
+
+    sqlx::query(
+        "INSERT INTO webauthn_credentials (credential_id, user_id, passkey_data) VALUES (?, ?, ?)",
+    )
+    .bind(&credential_id)
+    .bind(user_id)
+    .bind(&passkey_data)
+    .execute(&state.db_pool)
+    .await
+    .map_err(AppError::from)?;
+
+    let user: User = sqlx::query_as(
+        "SELECT user_id, username, password_hash, role FROM users WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_one(&state.db_pool)
+    .await?;
+
+    tracing::info!("Passkey registered for new user {} ({})", user.username, user_id);
+
+    let token = create_jwt(user.user_id, user.role, &state.keys, state.config.jwt_expires_in)
+        .map_err(|e| {
+            tracing::error!("Failed to generate JWT for user {}: {}", user.user_id, e);
+            AppError::Internal("Failed to generate token".to_string())
+        })?;
+    let refresh_token = refresh_tokens::issue(
+        &state.db_pool,
+        user.user_id,
+        state.config.refresh_token_expires_in_days,
+    )
+    .await?;
+    let session_max_age = state.config.refresh_token_expires_in_days * 24 * 60 * 60;
+    let jar = jar
+        .add(build_auth_cookie(token.clone(), state.config.jwt_maxage))
+        .add(build_refresh_cookie(refresh_token.clone(), session_max_age))
+        .add(build_csrf_cookie(generate_csrf_token(), session_max_age));
+
+    Ok((
+        jar,
+        Json(AuthToken {
+            token,
+            token_type: "Bearer".to_string(),
+            refresh_token,
+        }),
+    ))
+}
+
+/// パスキーログイン開始 (POST /auth/webauthn/login/start)
+#[utoipa::path(
+    post,
+    path = "/api/auth/webauthn/login/start",
+    tag = "Auth",
+    request_body(
+        content = WebauthnLoginStartPayload,
+        example = json!({"username": "testuser"})
+    ),
+    responses(
+        (status = 200, description = "認証セレモニーを開始", body = WebauthnChallenge),
+        (status = 401, description = "ユーザーが存在しない、またはパスキー未登録"),
+        (status = 500, description = "サーバーエラー")
+    )
+)]
+async fn login_start(
+    State(state): State<AppState>,
+    Json(payload): Json<WebauthnLoginStartPayload>,
+) -> Result<Json<WebauthnChallenge>, AppError> {
+    let user_id: Option<(i64,)> = sqlx::query_as("SELECT user_id FROM users WHERE username = ?")
+        .bind(&payload.username)
+        .fetch_optional(&state.db_pool)
+        .await?;
+
+    // ユーザー列挙を避けるため、「ユーザーが存在しない」と「パスキー未登録」は
+    // 区別せず同じ 401 として返す (パスワードログインの InvalidCredentials と同じ方針)
+    let (user_id,) = user_id.ok_or_else(|| {
+        tracing::warn!(
+            "WebAuthn login failed (user not found): {}",
+            payload.username
+        );
+        AppError::InvalidCredentials
+    })?;
+
+    let credentials = fetch_passkeys(&state, user_id).await?;
+    if credentials.is_empty() {
+        tracing::warn!(
+            "WebAuthn login failed (no passkeys registered): {}",
+            payload.username
+        );
+        return Err(AppError::InvalidCredentials);
+    }
+
+    let (challenge_id, rcr) = state.webauthn.start_authentication(credentials).await?;
+
+    Ok(Json(WebauthnChallenge {
+        challenge_id,
+        options: serde_json::to_value(rcr)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize challenge: {}", e)))?,
+    }))
+}
+
+/// パスキーログイン完了 (POST /auth/webauthn/login/finish)
+///
+/// `webauthn-rs` が signature counter の単調増加を検証するため、
+/// ここで更新後のカウンタをDBに書き戻し、クローンされた認証器による
+/// 将来のリプレイを検知できるようにします。
+#[utoipa::path(
+    post,
+    path = "/api/auth/webauthn/login/finish",
+    tag = "Auth",
+    request_body(content = WebauthnLoginFinishPayload),
+    responses(
+        (status = 200, description = "ログイン成功", body = AuthToken),
+        (status = 400, description = "セレモニーの検証に失敗 (クローン検知を含む)"),
+        (status = 500, description = "サーバーエラー")
+    )
+)]
+async fn login_finish(
+    State(state): State<AppState>,
+    jar: CookieJar,
+    Json(payload): Json<WebauthnLoginFinishPayload>,
+) -> Result<(CookieJar, Json<AuthToken>), AppError> {
+    let credential: PublicKeyCredential = serde_json::from_value(payload.credential)
+        .map_err(|e| AppError::Validation(format!("Invalid credential payload: {}", e)))?;
+
+    let credential_id = URL_SAFE_NO_PAD.encode(credential.raw_id.as_ref());
+
+    let stored: (i64, String) = sqlx::query_as(
+        "SELECT user_id, passkey_data FROM webauthn_credentials WHERE credential_id = ?",
+    )
+    .bind(&credential_id)
+    .fetch_optional(&state.db_pool)
+    .await?
+    .ok_or_else(|| {
+        tracing::warn!("WebAuthn login failed (unknown credential id)");
+        AppError::InvalidCredentials
+    })?;
+    let (user_id, passkey_data) = stored;
+
+    let mut passkey: Passkey = serde_json::from_str(&passkey_data).map_err(|e| {
+        tracing::error!("Failed to deserialize stored passkey: {}", e);
+        AppError::Internal("Corrupted passkey data".to_string())
+    })?;
+
+    let auth_result = state
+        .webauthn
+        .finish_authentication(&payload.challenge_id, &credential)
+        .await?;
+
+    // signature counter を更新 (単調増加していなければ `webauthn-rs` が
+    // 既に `finish_authentication` の時点でクローン検知として弾いている)
+    if passkey.update_credential(&auth_result).unwrap_or(false) {
+        let updated_data = serde_json::to_string(&passkey)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize passkey: {}", e)))?;
+        sqlx::query("UPDATE webauthn_credentials SET passkey_data = ? WHERE credential_id = ?")
+            .bind(&updated_data)
+            .bind(&credential_id)
+            .execute(&state.db_pool)
+            .await
+            .map_err(AppError::from)?;
+    }
+
+    let user: User = sqlx::query_as(
+        "SELECT user_id, username, password_hash, role FROM users WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_optional(&state.db_pool)
+    .await?
+    .ok_or_else(|| {
+        tracing::warn!("WebAuthn login succeeded for deleted user {}", user_id);
+        AppError::NotFound("User associated with credential")
+    })?;
+
+    tracing::info!("User logged in via passkey: {}", user.username);
+
+    let token = create_jwt(user.user_id, user.role, &state.keys, state.config.jwt_expires_in)
+        .map_err(|e| {
+            tracing::error!("Failed to generate JWT for user {}: {}", user.user_id, e);
+            AppError::Internal("Failed to generate token".to_string())
+        })?;
+    let refresh_token = refresh_tokens::issue(
+        &state.db_pool,
+        user.user_id,
+        state.config.refresh_token_expires_in_days,
+    )
+    .await?;
+    let session_max_age = state.config.refresh_token_expires_in_days * 24 * 60 * 60;
+    let jar = jar
+        .add(build_auth_cookie(token.clone(), state.config.jwt_maxage))
+        .add(build_refresh_cookie(refresh_token.clone(), session_max_age))
+        .add(build_csrf_cookie(generate_csrf_token(), session_max_age));
+
+    Ok((
+        jar,
+        Json(AuthToken {
+            token,
+            token_type: "Bearer".to_string(),
+            refresh_token,
+        }),
+    ))
+}
@@ -1,9 +1,19 @@
 // state.rs
 use crate::auth::Keys;
+use crate::config::Config;
+use crate::crawl_jobs::CrawlJobQueue;
+use crate::recommend::TfIdfIndex;
+use crate::webauthn::WebauthnService;
 use sqlx::{Pool, Sqlite};
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db_pool: Pool<Sqlite>,
     pub keys: Keys,
+    pub config: Config,
+    pub webauthn: WebauthnService,
+    pub crawl_jobs: CrawlJobQueue,
+    /// 起動時に一度だけ構築するTF-IDF推薦インデックス
+    pub recommender: Arc<TfIdfIndex>,
 }
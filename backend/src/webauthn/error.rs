@@ -0,0 +1,53 @@
+// src/webauthn/error.rs
+use std::error::Error as StdError;
+use std::fmt;
+use webauthn_rs::prelude::WebauthnError;
+
+/// WebAuthnモジュール専用のエラー型
+#[derive(Debug)]
+pub(crate) enum WebauthnCeremonyError {
+    /// 指定されたチャレンジIDに対応する進行中のセレモニーが見つからない
+    /// (タイムアウト、二重送信、存在しないIDなど)
+    ChallengeNotFound,
+    /// ブラウザから送られてきたペイロードを期待する型にデシリアライズできない
+    InvalidPayload(serde_json::Error),
+    /// `webauthn-rs` によるセレモニー検証自体の失敗
+    /// (署名不正、チャレンジ不一致、signature counterが単調増加していない、等)
+    Ceremony(WebauthnError),
+}
+
+impl fmt::Display for WebauthnCeremonyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebauthnCeremonyError::ChallengeNotFound => {
+                write!(f, "No in-progress WebAuthn ceremony for this challenge id")
+            }
+            WebauthnCeremonyError::InvalidPayload(e) => {
+                write!(f, "Invalid WebAuthn payload: {}", e)
+            }
+            WebauthnCeremonyError::Ceremony(e) => write!(f, "WebAuthn ceremony failed: {}", e),
+        }
+    }
+}
+
+impl StdError for WebauthnCeremonyError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            WebauthnCeremonyError::InvalidPayload(e) => Some(e),
+            WebauthnCeremonyError::Ceremony(e) => Some(e),
+            WebauthnCeremonyError::ChallengeNotFound => None,
+        }
+    }
+}
+
+impl From<WebauthnError> for WebauthnCeremonyError {
+    fn from(e: WebauthnError) -> Self {
+        WebauthnCeremonyError::Ceremony(e)
+    }
+}
+
+impl From<serde_json::Error> for WebauthnCeremonyError {
+    fn from(e: serde_json::Error) -> Self {
+        WebauthnCeremonyError::InvalidPayload(e)
+    }
+}
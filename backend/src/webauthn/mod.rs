@@ -0,0 +1,134 @@
+// src/webauthn/mod.rs
+
+mod error;
+
+pub(crate) use error::WebauthnCeremonyError;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use webauthn_rs::prelude::*;
+
+/// パスキー (WebAuthn) の登録・認証セレモニーを扱うサービス
+///
+/// `Webauthn` 自体は起動時に決まる不変の設定値なので `Keys` と同様に
+/// 一度だけ構築して `AppState` に載せます。セレモニーの進行中状態
+/// (チャレンジに対応する `PasskeyRegistration`/`PasskeyAuthentication`) は
+/// 短命なサーバー側ステートとして、チャレンジIDをキーにメモリ上へ
+/// 保持します (`crawler::politeness` のレートリミッタと同じ
+/// `Mutex<HashMap<...>>` のパターン)。
+#[derive(Clone)]
+pub struct WebauthnService {
+    webauthn: Arc<Webauthn>,
+    registrations: Arc<Mutex<HashMap<String, (String, PasskeyRegistration)>>>,
+    authentications: Arc<Mutex<HashMap<String, PasskeyAuthentication>>>,
+}
+
+impl WebauthnService {
+    /// main.rs で初期化時に呼び出す
+    pub fn new(rp_id: &str, rp_origin: &str) -> Self {
+        let origin = Url::parse(rp_origin).expect("WEBAUTHN_ORIGIN must be a valid URL");
+        let webauthn = WebauthnBuilder::new(rp_id, &origin)
+            .expect("Failed to configure WebAuthn relying party")
+            .rp_name("PaperDeck")
+            .build()
+            .expect("Failed to build Webauthn instance");
+
+        Self {
+            webauthn: Arc::new(webauthn),
+            registrations: Arc::new(Mutex::new(HashMap::new())),
+            authentications: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// 登録セレモニーを開始する。
+    ///
+    /// このエンドポイントは未認証で公開されているため、新規アカウント作成
+    /// 専用とする (既存アカウントへのパスキー追加は、この呼び出し時点では
+    /// まだDBにユーザー行が存在しないため扱わない)。そのため、WebAuthnが
+    /// 要求するユーザーハンドルも `users.user_id` からではなく、セレモニー
+    /// 限りの乱数 `Uuid` から生成する。除外リストも、まだ1件もパスキーを
+    /// 持たない新規アカウントである以上、渡す必要がない。
+    pub async fn start_registration(
+        &self,
+        username: &str,
+    ) -> Result<(String, CreationChallengeResponse), WebauthnCeremonyError> {
+        let user_handle = Uuid::new_v4();
+
+        let (ccr, reg_state) = self
+            .webauthn
+            .start_passkey_registration(user_handle, username, username, None)?;
+
+        let challenge_id = Uuid::new_v4().to_string();
+        self.registrations
+            .lock()
+            .await
+            .insert(challenge_id.clone(), (username.to_string(), reg_state));
+
+        Ok((challenge_id, ccr))
+    }
+
+    /// 登録セレモニーを完了し、DBに永続化すべき `(username, Passkey)` を返す
+    ///
+    /// ユーザー行自体はここではまだ作成しない。呼び出し側がこの結果を受けて
+    /// `users` に新規行をINSERTし、その `user_id` で `webauthn_credentials`
+    /// を紐付ける責任を持つ。
+    pub async fn finish_registration(
+        &self,
+        challenge_id: &str,
+        credential: &RegisterPublicKeyCredential,
+    ) -> Result<(String, Passkey), WebauthnCeremonyError> {
+        let (username, reg_state) = self
+            .registrations
+            .lock()
+            .await
+            .remove(challenge_id)
+            .ok_or(WebauthnCeremonyError::ChallengeNotFound)?;
+
+        let passkey = self
+            .webauthn
+            .finish_passkey_registration(credential, &reg_state)?;
+        Ok((username, passkey))
+    }
+
+    /// 認証 (ログイン) セレモニーを開始する
+    pub async fn start_authentication(
+        &self,
+        credentials: Vec<Passkey>,
+    ) -> Result<(String, RequestChallengeResponse), WebauthnCeremonyError> {
+        let (rcr, auth_state) = self.webauthn.start_passkey_authentication(&credentials)?;
+
+        let challenge_id = Uuid::new_v4().to_string();
+        self.authentications
+            .lock()
+            .await
+            .insert(challenge_id.clone(), auth_state);
+
+        Ok((challenge_id, rcr))
+    }
+
+    /// 認証セレモニーを完了する。
+    ///
+    /// `webauthn-rs` が署名検証の一部として signature counter の単調増加を
+    /// 確認しており、クローンされた認証器からのリプレイが疑われる場合は
+    /// ここで `WebauthnCeremonyError::Ceremony` として弾かれます。
+    /// 呼び出し側は、返り値の `AuthenticationResult` の `counter()` で
+    /// 新しいカウンタ値をDBに書き戻す必要があります。
+    pub async fn finish_authentication(
+        &self,
+        challenge_id: &str,
+        credential: &PublicKeyCredential,
+    ) -> Result<AuthenticationResult, WebauthnCeremonyError> {
+        let auth_state = self
+            .authentications
+            .lock()
+            .await
+            .remove(challenge_id)
+            .ok_or(WebauthnCeremonyError::ChallengeNotFound)?;
+
+        let result = self
+            .webauthn
+            .finish_passkey_authentication(credential, &auth_state)?;
+        Ok(result)
+    }
+}